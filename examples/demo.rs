@@ -52,6 +52,10 @@ impl Listable for MyListItem {
         self.height
     }
 
+    fn highlighted_height(&self) -> usize {
+        3 + self.content.len()
+    }
+
     fn highlight(self) -> Self {
         self.style(THEME.selection).expand()
     }
@@ -62,7 +66,7 @@ impl Widget for MyListItem {
         let mut lines = vec![Line::styled(self.title, self.style)];
         if self.expand {
             lines.push(Line::from(String::new()));
-            lines.extend(self.content.into_iter().map(|x| Line::from(x)));
+            lines.extend(self.content.into_iter().map(Line::from));
             lines.push(Line::from(String::new()));
         }
         Paragraph::new(lines)
@@ -124,7 +128,7 @@ pub struct App {
 }
 
 impl App {
-    pub fn new() -> App {
+    fn new() -> App {
         let state = ListState::default();
         App { state }
     }
@@ -92,6 +92,7 @@ impl Widget for TabItem {
 }
 
 #[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
 enum ListElements<'a> {
     TabItem(TabItem),
     ParagraphItem(ParagraphItem<'a>),
@@ -175,7 +176,7 @@ pub struct App<'a> {
 }
 
 impl<'a> App<'a> {
-    pub fn new() -> App<'a> {
+    fn new() -> App<'a> {
         let items = vec![
             ListElements::ParagraphItem(ParagraphItem::new("Height: 4", 4)),
             ListElements::TabItem(TabItem::new(vec![
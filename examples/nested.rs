@@ -0,0 +1,268 @@
+//! A list whose items contain lists. The inner lists own their state
+//! through a shared reference, so that the state survives the items
+//! being cloned and consumed on every render. The outer list routes the
+//! navigation into the inner list of the selected group once it is entered.
+//!
+//! Keys: Up/Down to navigate, Right/Enter to enter a group, Left/Esc
+//! to leave it, q to quit.
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
+};
+use std::{cell::RefCell, error::Error, io, rc::Rc};
+use tui_widget_list::{List, ListState, Listable};
+
+/// The maximum number of rows of an inner list.
+const MAX_CHILD_ROWS: usize = 4;
+
+/// An item of an inner list.
+#[derive(Debug, Clone)]
+pub struct ChildItem {
+    text: String,
+    style: Style,
+}
+
+impl ChildItem {
+    fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: Style::default(),
+        }
+    }
+}
+
+impl Listable for ChildItem {
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn highlight(mut self) -> Self {
+        self.style = Style::default().bg(Color::Cyan);
+        self
+    }
+}
+
+impl Widget for ChildItem {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(format!("  - {}", self.text))
+            .style(self.style)
+            .render(area, buf);
+    }
+}
+
+/// An item of the outer list that contains an inner list.
+#[derive(Debug, Clone)]
+pub struct GroupItem {
+    title: String,
+    children: Vec<ChildItem>,
+
+    /// The state of the inner list. It is shared with the app, so that
+    /// changes of the inner list on render are not lost.
+    state: Rc<RefCell<ListState>>,
+
+    /// Whether the inner list is shown.
+    expanded: bool,
+
+    /// Whether the group is selected in the outer list.
+    selected: bool,
+}
+
+impl GroupItem {
+    fn new(title: impl Into<String>, children: Vec<ChildItem>) -> Self {
+        Self {
+            title: title.into(),
+            children,
+            state: Rc::new(RefCell::new(ListState::default())),
+            expanded: false,
+            selected: false,
+        }
+    }
+}
+
+impl Listable for GroupItem {
+    fn height(&self) -> usize {
+        if self.expanded {
+            1 + self.children.len().min(MAX_CHILD_ROWS)
+        } else {
+            1
+        }
+    }
+
+    fn highlight(mut self) -> Self {
+        self.selected = true;
+        self
+    }
+}
+
+impl Widget for GroupItem {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let marker = if self.expanded { "v" } else { ">" };
+        // The outer highlight is only shown while the group is not entered
+        let style = if self.selected && !self.expanded {
+            Style::default().bg(Color::Cyan)
+        } else {
+            Style::default().bold()
+        };
+        let title = Rect { height: 1, ..area };
+        Paragraph::new(format!("{marker} {}", self.title))
+            .style(style)
+            .render(title, buf);
+
+        if self.expanded && area.height > 1 {
+            let inner = Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            };
+            let list = List::new(self.children);
+            list.render(inner, buf, &mut self.state.borrow_mut());
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn main() -> Result<()> {
+    let mut terminal = init_terminal()?;
+
+    let app = App::new();
+    run_app(&mut terminal, app).unwrap();
+
+    reset_terminal()?;
+    terminal.show_cursor()?;
+
+    Ok(())
+}
+
+/// Initializes the terminal.
+fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    enable_raw_mode()?;
+
+    let backend = CrosstermBackend::new(io::stdout());
+
+    let mut terminal = Terminal::new(backend)?;
+    terminal.hide_cursor()?;
+
+    panic_hook();
+
+    Ok(terminal)
+}
+
+/// Resets the terminal.
+fn reset_terminal() -> Result<()> {
+    disable_raw_mode()?;
+    crossterm::execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    Ok(())
+}
+
+/// Shutdown gracefully
+fn panic_hook() {
+    let original_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |panic| {
+        reset_terminal().unwrap();
+        original_hook(panic);
+    }));
+}
+
+pub struct App {
+    groups: Vec<GroupItem>,
+    state: ListState,
+
+    /// Whether the selected group is entered, i.e. whether the
+    /// navigation is routed into its inner list.
+    entered: bool,
+}
+
+impl App {
+    fn new() -> App {
+        let groups = (1..=8)
+            .map(|i| {
+                let children = (1..=6)
+                    .map(|j| ChildItem::new(format!("Item {i}.{j}")))
+                    .collect();
+                GroupItem::new(format!("Group {i}"), children)
+            })
+            .collect();
+        App {
+            groups,
+            state: ListState::default(),
+            entered: false,
+        }
+    }
+
+    /// Returns the group that is selected in the outer list.
+    fn selected_group(&mut self) -> Option<&mut GroupItem> {
+        self.state
+            .selected()
+            .and_then(|index| self.groups.get_mut(index))
+    }
+
+    /// Hands the focus to the inner list of the selected group.
+    fn enter(&mut self) {
+        let Some(group) = self.selected_group() else {
+            return;
+        };
+        group.expanded = true;
+        let mut state = group.state.borrow_mut();
+        if state.selected().is_none() {
+            state.select(Some(0));
+        }
+        drop(state);
+        self.entered = true;
+    }
+
+    /// Hands the focus back to the outer list.
+    fn leave(&mut self) {
+        if let Some(group) = self.selected_group() {
+            group.expanded = false;
+        }
+        self.entered = false;
+    }
+
+    fn next(&mut self) {
+        match self.selected_group() {
+            Some(group) if group.expanded => group.state.borrow_mut().next(),
+            _ => self.state.next(),
+        }
+    }
+
+    fn previous(&mut self) {
+        match self.selected_group() {
+            Some(group) if group.expanded => group.state.borrow_mut().previous(),
+            _ => self.state.previous(),
+        }
+    }
+}
+
+pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|f| ui(f, &mut app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                match key.code {
+                    KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Up => app.previous(),
+                    KeyCode::Down => app.next(),
+                    KeyCode::Right | KeyCode::Enter if !app.entered => app.enter(),
+                    KeyCode::Left | KeyCode::Esc if app.entered => app.leave(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn ui(f: &mut Frame, app: &mut App) {
+    let block = Block::default().borders(Borders::ALL).title("Groups");
+    let list = List::new(app.groups.clone()).block(block);
+    f.render_stateful_widget(list, f.size(), &mut app.state);
+}
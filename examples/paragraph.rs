@@ -103,7 +103,7 @@ pub struct App<'a> {
 }
 
 impl<'a> App<'a> {
-    pub fn new() -> App<'a> {
+    fn new() -> App<'a> {
         let items = vec![
             ParagraphItem::new("Height: 4", 4),
             ParagraphItem::new("Height: 6", 6),
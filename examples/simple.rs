@@ -125,7 +125,7 @@ pub struct App<'a> {
 }
 
 impl<'a> App<'a> {
-    pub fn new() -> App<'a> {
+    fn new() -> App<'a> {
         let items = vec![
             ListItem::new(Text::from("Item 1")),
             ListItem::new(Text::from("Item 2")),
@@ -0,0 +1,28 @@
+/// The context in which an item is rendered. The [`List`](crate::List)
+/// passes it to every item on the viewport in
+/// [`Listable::context`](crate::Listable::context).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ItemContext {
+    /// The index of the item in the list.
+    pub index: usize,
+
+    /// Whether the item is the first item of the list. This refers to the
+    /// whole list, not to the items on the viewport.
+    pub is_first: bool,
+
+    /// Whether the item is the last item of the list. This refers to the
+    /// whole list, not to the items on the viewport.
+    pub is_last: bool,
+
+    /// Whether the list wraps its items. If true, the item was measured
+    /// with [`Listable::height_for_width`](crate::Listable::height_for_width)
+    /// and is expected to wrap its content to the width of the list.
+    pub wrap: bool,
+
+    /// The number of columns by which the content of the items is scrolled
+    /// to the left, see [`ListState::scroll_right`](crate::ListState::scroll_right).
+    /// The same for all items, so that columns move together. The list
+    /// clips the items to its width.
+    pub h_offset: u16,
+}
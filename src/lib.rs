@@ -71,14 +71,75 @@
 //! - **style**: The base style of the list.
 //! - **block**: An optional outer block around the list.
 //! - **truncate**: If truncate is true, the first and last elements are truncated to fill the entire screen. True by default.
+//! - **truncate_top**, **truncate_bottom**: Whether the first and the last element respectively may be truncated. True by default.
+//! - **highlight_theme**: A theme that is passed to the selected item in `Listable::highlight_with`.
+//! - **wrap**: If wrap is true, items are measured with `Listable::height_for_width` and are expected to wrap their content. False by default.
+//! - **separator**: An optional separator drawn beneath items whose `separator_after` is true, or beneath all items if **separate_all** is true.
+//! - **overscroll_end**: If overscroll end is true, the last item can be scrolled to the top of the viewport. False by default.
+//! - **highlight_symbol**: An optional symbol drawn left of the selected item, styled with **highlight_symbol_style**.
+//! - **highlight_full_width**: If true, the highlight background spans the full width of the selected rows. False by default.
+//! - **block_title_fn**: An optional function that computes the title of the block from the selected item.
+//! - **selected**: An optional selection that overrides the selection of the state on every render.
+//! - **start_corner**: The corner at which the first item is laid out. If it is a bottom corner, the items are stacked upwards. Top left by default.
+//! - **reversed**: If reversed is true, the items are laid out in reverse order. False by default.
+//! - **on_clamp**: An optional function that is called with the old and the new index if an out of bounds selection is clamped on render.
+//! - **clamp_item_height**: Clamps the heights of all items to a minimum and a maximum. Unclamped by default.
+//! - **highlight_style**: A style that is patched over the selected item after it is rendered.
+//! - **frozen_bottom**: The number of items at the end of the list that are pinned to the bottom of the viewport. Zero by default.
+//! - **assume_width**: Measures the heights of wrapped items once for a fixed width instead of on every render.
+//! - **scroll_indicators**: If true, indicators are drawn if items are hidden above or below the viewport. A click on them can be detected with `ListState::element_at`. False by default.
+//! - **debug_overlay**: If true, the numbers of the layout are drawn in the top right corner for debugging. False by default.
+//! - **gap_style**: The style of the separator rows and of the empty rows below the items.
+//! - **scroll_strategy**, **programmatic_scroll_strategy**: How the viewport follows the selection if the user navigates and if an item is selected programmatically respectively. The viewport scrolls only at the edges by default.
+//! - **default_item_height**: The height of items whose height is zero. Zero by default.
+//! - **flow_columns**: The number of columns the items flow into, filling one column after another. One by default.
+//! - **highlight_symbol_width**: A fixed width of the gutter of the highlight symbols. Symbols that are wider are cut off. As wide as the widest symbol by default.
+//! - **drop_indicator**: An optional line drawn at the drop target of the state, see `ListState::set_drop_target`.
+//! - **auto_scroll**: If auto scroll is false, the viewport does not follow the selection and only scrolls explicitly. True by default.
+//! - **compact**: If compact is true, the items are shown in their compact form, see `Listable::compact`. False by default.
+//! - **highlightable**: If highlightable is false, no item is highlighted or marked and the navigation of the state does nothing, e.g. to display a read-only list that only scrolls. True by default.
+//! - **scrollbar**: An optional vertical scrollbar drawn in the right column inside the block. A click on its track scrolls a page, see `ListState::click`.
+//! - **scroll_padding**: The number of rows of other items that are kept visible above and below the selected item. Zero by default.
+//! - **direction**: The direction in which the items are laid out. If horizontal, the items are laid out from left to right and measured by `Listable::width`, or take the full width if they have none. Vertical by default.
+//! - **spacing**: The number of blank rows between the items, in addition to the separators. Zero by default.
+//! - **sticky_header**: The number of items at the start of the list that are pinned to the top of the viewport. They are skipped by the navigation. Zero by default.
 //!
 //! The behaviour of [`ListState`] can be modified
 //! - **circular**: Whether the selection is circular, i.e. if true, the first item is selected after the last. True by default.
+//! - **selection_anchor**: How `ListState::on_len_change` adjusts the selection if the number of items changes, e.g. keeping the distance to the end. Keeps the index by default.
+//! - **wrap_policy**: Which operations wrap the selection, e.g. single steps but not paging. All operations wrap by default.
+//!
+//! ## Styling
+//! The styles of an item are applied in this order, each on top of the previous ones:
+//! 1. the base style of the list, see `List::style`, unless the item is opaque, see `Listable::opaque`,
+//! 2. the style of the item, see `Listable::item_style`,
+//! 3. the styles the item sets when it renders itself, including its highlight, see `Listable::highlight_with`,
+//! 4. the highlight style of the list if the item is selected, see `List::highlight_style`.
+//!
+//! ## Nested lists
+//! Since items are widgets, an item may render a [`List`] of its own. As items are cloned and consumed on
+//! every render, such an item should share the state of its inner list with the app, e.g. through an
+//! `Rc<RefCell<ListState>>`. The app decides which list has the focus and routes the navigation into the
+//! state of the inner list while its item is entered. See the `nested` example.
+//!
+//! ## Features
+//! - **metrics**: Records statistics about each render, see `ListState::last_render_stats`. Disabled by default.
 //!
 //!![](img/demo.gif)
+pub mod context;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod state;
+pub mod theme;
 pub mod traits;
 pub mod widget;
-pub use state::ListState;
-pub use traits::Listable;
+pub use context::ItemContext;
+#[cfg(feature = "metrics")]
+pub use metrics::RenderStats;
+pub use state::{
+    ItemLayout, JumpMode, ListDirection, ListElement, ListEvent, ListState, ScrollStrategy,
+    SelectionAnchor, WrapPolicy,
+};
+pub use theme::HighlightTheme;
+pub use traits::{Keyed, Listable};
 pub use widget::List;
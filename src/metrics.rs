@@ -0,0 +1,15 @@
+/// Statistics about the last render of a `List`, see
+/// `ListState::last_render_stats`. Only available with the
+/// `metrics` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RenderStats {
+    /// The number of items that were rendered on the viewport.
+    pub items_rendered: usize,
+
+    /// The number of items whose height was measured.
+    pub items_measured: usize,
+
+    /// The time it took to measure the items and to lay them out
+    /// on the viewport, in microseconds.
+    pub layout_micros: u64,
+}
@@ -1,19 +1,338 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use ratatui::{prelude::Rect, widgets::ScrollbarState};
+
+/// Which edges of the viewport truncate items that do not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Truncate {
+    /// Whether the first item on the viewport may be truncated.
+    pub(crate) top: bool,
+
+    /// Whether the last item on the viewport may be truncated.
+    pub(crate) bottom: bool,
+}
+
+/// The animation of an item that was inserted or removed by
+/// `List::update_keyed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Transition {
+    /// Whether the item was removed and is about to disappear.
+    pub(crate) leaving: bool,
+
+    /// The number of renders until the animation is finished.
+    pub(crate) frames: u16,
+}
+
+impl Default for Truncate {
+    fn default() -> Self {
+        Self::both(true)
+    }
+}
+
+impl Truncate {
+    /// Truncate at both edges or none.
+    pub(crate) fn both(truncate: bool) -> Self {
+        Self {
+            top: truncate,
+            bottom: truncate,
+        }
+    }
+}
+
+/// How the viewport follows the selection after a jump.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum JumpMode {
+    /// The offset snaps to the selection immediately.
+    #[default]
+    Instant,
+
+    /// The offset scrolls towards the selection by one item per render.
+    Scroll,
+}
+
+/// How the viewport follows the selection, see `List::scroll_strategy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ScrollStrategy {
+    /// The viewport scrolls as little as possible, i.e. only once the
+    /// selection reaches an edge of the viewport.
+    #[default]
+    Edge,
+
+    /// The viewport scrolls the selection to the center of the viewport,
+    /// as far as the items allow, once the selection is not fully shown.
+    Center,
+
+    /// The viewport keeps the selection in the center of the viewport on
+    /// every move, as far as the items allow, so that the items flow under
+    /// the selection. Only at the top and the bottom of the list the
+    /// selection moves away from the center.
+    AlwaysCenter,
+}
+
+/// The direction in which the items are laid out, see `List::direction`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ListDirection {
+    /// The items are laid out from top to bottom and measured by their
+    /// heights.
+    #[default]
+    Vertical,
+
+    /// The items are laid out from left to right and measured by their
+    /// widths, see `Listable::width`.
+    Horizontal,
+}
+
+/// How the selection is adjusted if the number of items changes, see
+/// [`ListState::on_len_change`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SelectionAnchor {
+    /// The selection keeps its index, as far as the list is long enough.
+    #[default]
+    KeepIndex,
+
+    /// The selection keeps its distance to the end of the list, e.g. in a
+    /// log to which items are appended.
+    KeepFromEnd,
+
+    /// The selection keeps its relative position in the list.
+    KeepRatio,
+}
+
+/// The layout of an item that was shown in the last render,
+/// see [`ListState::layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemLayout {
+    /// The index of the item.
+    pub index: usize,
+
+    /// The area of the item on the screen, including the gutter of the
+    /// highlight symbol and the rows of the separator beneath the item.
+    pub rect: Rect,
+
+    /// The number of rows of the item that are cut off at the top.
+    pub truncated_top: usize,
+
+    /// The number of rows of the item that are cut off at the bottom.
+    pub truncated_bottom: usize,
+}
+
+/// An element of the list at a position on the screen,
+/// see [`ListState::element_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListElement {
+    /// The indicator that items are hidden above the viewport.
+    TopIndicator,
+
+    /// The item with the given index.
+    Item(usize),
+
+    /// The indicator that items are hidden below the viewport.
+    BottomIndicator,
+
+    /// The track of the scrollbar above its thumb.
+    TrackAbove,
+
+    /// The thumb of the scrollbar.
+    Thumb,
+
+    /// The track of the scrollbar below its thumb.
+    TrackBelow,
+}
+
+/// A navigation event of a [`ListState`], see [`ListState::set_event_sink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListEvent {
+    /// The selection changed to the given item.
+    Selected(Option<usize>),
+
+    /// The viewport was scrolled to the given offset without changing
+    /// the selection.
+    Scrolled(usize),
+
+    /// The selection wrapped around an end of the list to the given item.
+    Wrapped(usize),
+
+    /// The selection moved by a page to the given item.
+    Paged(usize),
+
+    /// The selection stopped at an end of the list since it does not wrap.
+    HitEnd,
+}
+
+/// Receives the navigation events of a [`ListState`]. Shared between
+/// clones of the state. The state stays `Send` with an event sink.
+#[derive(Clone)]
+pub(crate) struct EventSink(Arc<Mutex<dyn FnMut(ListEvent) + Send>>);
+
+impl fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventSink")
+    }
+}
+
+/// Which operations wrap the selection around the ends of the list.
+/// Every operation wraps by default, see [`ListState::circular`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WrapPolicy {
+    /// Whether single steps wrap, e.g. [`ListState::next`].
+    pub step: bool,
+
+    /// Whether paging wraps, e.g. [`ListState::next_visible_page`] and
+    /// [`ListState::page_down`].
+    pub page: bool,
+
+    /// Whether a search for an item wraps.
+    pub search: bool,
+}
+
+impl WrapPolicy {
+    /// A policy where either all or no operations wrap.
+    #[must_use]
+    pub fn all(wrap: bool) -> Self {
+        Self {
+            step: wrap,
+            page: wrap,
+            search: wrap,
+        }
+    }
+}
+
+impl Default for WrapPolicy {
+    fn default() -> Self {
+        Self::all(true)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ListState {
     /// The selected item. If none, no item is selected.
     pub selected: Option<usize>,
 
+    /// The indices of the items that are selected in addition to
+    /// the cursor, e.g. in a multi-select list.
+    pub(crate) selected_items: HashSet<usize>,
+
+    /// The hashed key of the item whose selection should be restored.
+    pub(crate) remembered_key: Option<u64>,
+
     /// The index of the fist item on the screen
     pub(crate) offset: usize,
 
+    /// The number of rows of the first item on the screen that are
+    /// truncated at the top of the viewport.
+    pub(crate) top_clip: usize,
+
+    /// The number of columns by which the items are scrolled horizontally.
+    pub(crate) h_offset: u16,
+
     /// The number of elements of the list. This is necessary to correctly
     /// wrap the selection of items.
     pub(crate) num_elements: usize,
 
-    /// Whether the selection is circular. If circular, calling next on the
-    /// last element returns the first element, and calling previous on
-    /// the first element returns the last element.
-    non_circular: bool,
+    /// Which operations wrap the selection around the ends of the list.
+    /// If wrapping, calling next on the last element returns the first
+    /// element, and calling previous on the first element returns the last.
+    pub(crate) wrap_policy: WrapPolicy,
+
+    /// How the selection is adjusted if the number of items changes.
+    pub(crate) selection_anchor: SelectionAnchor,
+
+    /// The item that was activated and not yet taken.
+    pub(crate) activated: Option<usize>,
+
+    /// Whether the navigation does nothing, as the last render was of a
+    /// list that is not highlightable.
+    pub(crate) navigation_disabled: bool,
+
+    /// Whether the offset does not follow the selection, as the last render
+    /// was of a list without auto scroll.
+    pub(crate) auto_scroll_disabled: bool,
+
+    /// The indices of the items that were not selectable in the last render.
+    pub(crate) unselectable: HashSet<usize>,
+
+    /// The indices of the items that have a height of zero in the last
+    /// render. They are skipped by the navigation.
+    pub(crate) hidden: HashSet<usize>,
+
+    /// The heights of the items from the last render.
+    pub(crate) heights: Vec<usize>,
+
+    /// The number of items that were fully visible in the last render.
+    pub(crate) visible_items: usize,
+
+    /// The indices of the items that were shown in the last render.
+    pub(crate) visible_range: Range<usize>,
+
+    /// The layout of the items that were shown in the last render.
+    pub(crate) layout: Vec<ItemLayout>,
+
+    /// The positions of the top and the bottom scroll indicator in the
+    /// last render, together with the index of the item they jump to.
+    pub(crate) indicators: [Option<(u16, u16, usize)>; 2],
+
+    /// The area of the scrollbar in the last render.
+    pub(crate) scrollbar: Option<Rect>,
+
+    /// The height of the viewport from the last render.
+    pub(crate) viewport_height: Option<u16>,
+
+    /// The length of the viewport along the direction of the list from the
+    /// last render, i.e. its height, or its width if the list is horizontal.
+    /// The heights of the items are measured along the same direction.
+    pub(crate) viewport_length: Option<u16>,
+
+    /// Whether the list truncated its items in the last render.
+    pub(crate) truncate: Truncate,
+
+    /// Whether the list laid out its items in reverse in the last render.
+    pub(crate) reversed: bool,
+
+    /// The number of items that were pinned to the top in the last render.
+    /// The heights and the offset count only the items below them.
+    pub(crate) sticky_header: usize,
+
+    /// How the viewport followed the selection in the last render if the
+    /// user navigated.
+    pub(crate) scroll_strategy: ScrollStrategy,
+
+    /// How the viewport followed the selection in the last render if it
+    /// was selected programmatically.
+    pub(crate) programmatic_scroll_strategy: ScrollStrategy,
+
+    /// Whether the offset scrolls towards the selection over the next
+    /// renders instead of snapping to it.
+    pub(crate) scrolling: bool,
+
+    /// Whether the offset was scrolled explicitly. A detached offset does
+    /// not follow the selection until the selection changes.
+    pub(crate) detached: bool,
+
+    /// Whether the selection was last changed by navigating, e.g. with
+    /// [`Self::next`], instead of by selecting an item programmatically.
+    pub(crate) navigated: bool,
+
+    /// The animations of the items that were inserted or removed, by index.
+    pub(crate) transitions: HashMap<usize, Transition>,
+
+    /// The heights of wrapped items by width and content hash, see
+    /// `Listable::content_hash`.
+    pub(crate) height_cache: HashMap<(u16, u64), usize>,
+
+    /// The index at which a dragged item would be inserted, if any.
+    pub(crate) drop_target: Option<usize>,
+
+    /// Receives the navigation events, if any.
+    pub(crate) event_sink: Option<EventSink>,
+
+    /// The statistics of the last render.
+    #[cfg(feature = "metrics")]
+    pub(crate) render_stats: crate::RenderStats,
 }
 
 impl ListState {
@@ -23,13 +342,89 @@ impl ListState {
         self.num_elements = num_elements;
     }
 
+    /// Clamps the selection and the offset to a list of the given length,
+    /// e.g. after items were removed from the data outside of the list.
+    /// The list does this on render, but the state is consistent right
+    /// away after calling this. The selection is removed if the length is
+    /// zero. Also updates the number of elements.
+    pub fn clamp_to(&mut self, len: usize) {
+        self.num_elements = len;
+        let Some(last) = len.checked_sub(1) else {
+            self.selected = None;
+            self.offset = 0;
+            self.top_clip = 0;
+            return;
+        };
+        self.selected = self.selected.map(|index| index.min(last));
+        if self.offset > last {
+            self.offset = last;
+            self.top_clip = 0;
+        }
+    }
+
     /// If circular is True, the selection continues from the
     /// last item to the first when going down, and from the
     /// first item to the last when going up.
-    /// It is true by default.
+    /// It is true by default. Applies to all operations,
+    /// see [`Self::wrap_policy`] to configure them separately.
     #[must_use]
     pub fn circular(mut self, circular: bool) -> Self {
-        self.non_circular = !circular;
+        self.wrap_policy = WrapPolicy::all(circular);
+        self
+    }
+
+    /// Set which operations wrap the selection around the ends of the
+    /// list, e.g. to wrap single steps but to clamp paging.
+    #[must_use]
+    pub fn wrap_policy(mut self, wrap_policy: WrapPolicy) -> Self {
+        self.wrap_policy = wrap_policy;
+        self
+    }
+
+    /// Set how the selection is adjusted by [`Self::on_len_change`].
+    /// It is [`SelectionAnchor::KeepIndex`] by default.
+    #[must_use]
+    pub fn selection_anchor(mut self, anchor: SelectionAnchor) -> Self {
+        self.selection_anchor = anchor;
+        self
+    }
+
+    /// Adjusts the selection and the offset after the number of items
+    /// changed from `old_len` to `new_len` according to the selection
+    /// anchor, e.g. if items are added to or removed from the data by
+    /// index, without keys. With [`SelectionAnchor::KeepFromEnd`] the
+    /// offset keeps its distance to the end as well. The selection is
+    /// removed if the list became empty.
+    pub fn on_len_change(&mut self, old_len: usize, new_len: usize) {
+        self.num_elements = new_len;
+        let Some(last) = new_len.checked_sub(1) else {
+            self.selected = None;
+            self.offset = 0;
+            self.top_clip = 0;
+            return;
+        };
+        let old_last = old_len.saturating_sub(1);
+        let adjust = |index: usize| match self.selection_anchor {
+            SelectionAnchor::KeepIndex => index,
+            SelectionAnchor::KeepFromEnd => last.saturating_sub(old_last.saturating_sub(index)),
+            SelectionAnchor::KeepRatio if old_last == 0 => 0,
+            SelectionAnchor::KeepRatio => (index * last + old_last / 2) / old_last,
+        };
+        self.selected = self.selected.map(|index| adjust(index).min(last));
+        if self.selection_anchor == SelectionAnchor::KeepFromEnd {
+            self.offset = adjust(self.offset);
+        }
+        if self.offset > last {
+            self.offset = last;
+            self.top_clip = 0;
+        }
+    }
+
+    /// Select an item by its index when the state is created. The
+    /// selected item is brought into view on the first render.
+    #[must_use]
+    pub fn with_selected(mut self, index: Option<usize>) -> Self {
+        self.select(index);
         self
     }
 
@@ -39,58 +434,931 @@ impl ListState {
         self.selected
     }
 
-    /// Select an item by its index
+    /// Select an item by its index and bring it into view. An index beyond
+    /// the last item selects the last item, as far as the number of items is
+    /// known, see [`Self::set_num_elements`]. The offset is updated right away
+    /// with the heights of the last render, following the programmatic scroll
+    /// strategy, see [`Self::scroll_to_selection`], unless the list does not
+    /// scroll automatically. Before the list was rendered once, the selection
+    /// is brought into view on the first render. If none, the selection is
+    /// removed and the offset is reset.
     pub fn select(&mut self, index: Option<usize>) {
+        let index = match self.num_elements.checked_sub(1) {
+            Some(last) => index.map(|index| index.min(last)),
+            None => index,
+        };
+        self.set_selected(index);
+        if index.is_some() && !self.auto_scroll_disabled {
+            self.scroll_to_selection();
+        }
+    }
+
+    /// Sets the selection and emits an event if it changed. The offset
+    /// follows the selection on the next render.
+    pub(crate) fn set_selected(&mut self, index: Option<usize>) {
+        if self.selected != index {
+            self.emit(ListEvent::Selected(index));
+        }
         self.selected = index;
+        self.scrolling = false;
+        self.detached = false;
+        self.navigated = false;
         if index.is_none() {
             self.offset = 0;
         }
     }
 
+    /// Set a function that receives the navigation events of the state,
+    /// e.g. to log them or to record a session. Every navigation method
+    /// emits its events, e.g. [`Self::next`] emits a selection event and
+    /// an additional event if the selection wrapped or stopped at the end.
+    /// The function is shared between clones of the state.
+    pub fn set_event_sink(&mut self, sink: impl FnMut(ListEvent) + Send + 'static) {
+        self.event_sink = Some(EventSink(Arc::new(Mutex::new(sink))));
+    }
+
+    /// Passes the event to the event sink, if any.
+    fn emit(&self, event: ListEvent) {
+        if let Some(EventSink(sink)) = &self.event_sink {
+            if let Ok(mut sink) = sink.lock() {
+                sink(event);
+            }
+        }
+    }
+
+    /// Selects the selectable item that is nearest to the given index, see
+    /// `Listable::selectable`. If two items are equally near, the later
+    /// item is selected. The selection is not changed if no item is
+    /// selectable. Which items are selectable is known from the last render.
+    pub fn select_nearest(&mut self, index: usize) {
+        let Some(last) = self.num_elements.checked_sub(1) else {
+            return;
+        };
+        let index = index.min(last);
+        let nearest = (0..=last).find_map(|distance| {
+            let later = index.checked_add(distance).filter(|&i| i <= last);
+            let earlier = index.checked_sub(distance);
+            [later, earlier]
+                .into_iter()
+                .flatten()
+                .find(|i| !self.unselectable.contains(i))
+        });
+        if nearest.is_some() {
+            self.select(nearest);
+        }
+    }
+
+    /// Activates the selected item, e.g. if enter is pressed in a menu.
+    /// Activating does not change the selection. The activated item is
+    /// returned once by [`Self::take_activated`]. Does nothing if no item
+    /// is selected.
+    pub fn activate(&mut self) {
+        if self.selected.is_some() {
+            self.activated = self.selected;
+        }
+    }
+
+    /// Returns the item that was activated since the last call, see
+    /// [`Self::activate`].
+    pub fn take_activated(&mut self) -> Option<usize> {
+        self.activated.take()
+    }
+
+    /// Set the index at which a dragged item would be inserted, e.g. while
+    /// the items are reordered. The list draws its drop indicator in the
+    /// gap between the item with the index and the previous item, or
+    /// behind the last item if the index equals the number of items. See
+    /// `List::drop_indicator`.
+    pub fn set_drop_target(&mut self, index: Option<usize>) {
+        self.drop_target = index;
+    }
+
+    /// Returns the index at which a dragged item would be inserted, see
+    /// [`Self::set_drop_target`].
+    #[must_use]
+    pub fn drop_target(&self) -> Option<usize> {
+        self.drop_target
+    }
+
+    /// Toggles whether the item with the given index is part of the
+    /// multi-selection. Independent of the selected cursor item.
+    pub fn toggle_selection(&mut self, index: usize) {
+        if !self.selected_items.remove(&index) {
+            self.selected_items.insert(index);
+        }
+    }
+
+    /// Returns the indices of the items that are part of the multi-selection.
+    /// The indices are not adjusted if items are removed from the list.
+    #[must_use]
+    pub fn selected_items(&self) -> &HashSet<usize> {
+        &self.selected_items
+    }
+
+    /// Removes all items from the multi-selection.
+    pub fn clear_selections(&mut self) {
+        self.selected_items.clear();
+    }
+
+    /// Select an item by its index and bring it into view. With
+    /// [`JumpMode::Instant`] the offset is updated right away, with
+    /// [`JumpMode::Scroll`] the viewport scrolls towards the item by
+    /// one item on every render, so the user keeps their bearings.
+    /// Selecting another item stops the scrolling. Like [`Self::select`],
+    /// the index is clamped to the last item. Does nothing if the last
+    /// render was of a list that is not highlightable.
+    pub fn jump_to(&mut self, index: usize, mode: JumpMode) {
+        if self.navigation_disabled {
+            return;
+        }
+        let index = match self.num_elements.checked_sub(1) {
+            Some(last) => index.min(last),
+            None => index,
+        };
+        self.set_selected(Some(index));
+        match mode {
+            JumpMode::Instant => self.scroll_to_selection(),
+            JumpMode::Scroll => self.scrolling = true,
+        }
+    }
+
+    /// Whether an animation is in progress, e.g. the viewport scrolls
+    /// towards a jump target with [`JumpMode::Scroll`] or items fade in and
+    /// out after `List::update_keyed`. While this is true,
+    /// the list should be rendered again without waiting for input.
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        self.scrolling || self.transitions.values().any(|t| t.frames > 0)
+    }
+
     /// Selects the next element of the list. If circular is true,
-    /// calling next on the last element selects the first.
+    /// calling next on the last element selects the first. If none is
+    /// selected, the first element is selected.
     pub fn next(&mut self) {
-        if self.num_elements == 0 {
+        self.next_by(1);
+    }
+
+    /// Selects the previous element of the list. If circular is true,
+    /// calling previous on the first element selects the last. If none
+    /// is selected, the last element is selected.
+    pub fn previous(&mut self) {
+        self.previous_by(1);
+    }
+
+    /// Moves the selection down by the given number of elements. This is
+    /// the same as calling [`Self::next`] `n` times. If circular is true,
+    /// the selection wraps around, otherwise it stops at the last element.
+    pub fn next_by(&mut self, n: usize) {
+        self.move_down(n, self.wrap_policy.step);
+    }
+
+    /// Moves the selection down by the given number of elements and
+    /// wraps around if `wrap` is true.
+    fn move_down(&mut self, n: usize, wrap: bool) {
+        if self.num_elements == 0 || n == 0 || self.navigation_disabled {
             return;
         }
-        let i = match self.selected() {
-            Some(i) => {
-                if i >= self.num_elements - 1 {
-                    if self.non_circular {
-                        i
-                    } else {
-                        0
-                    }
-                } else {
-                    i + 1
+        let last = self.num_elements - 1;
+        // If none is selected, the first step selects the first element
+        let (i, n) = match self.selected() {
+            Some(i) => (i.min(last), n),
+            None => (0, n - 1),
+        };
+        let beyond = i.saturating_add(n) > last;
+        let i = if !wrap {
+            i.saturating_add(n).min(last)
+        } else {
+            (i + n % self.num_elements) % self.num_elements
+        };
+        let Some(i) = self.skip_hidden(i, true, wrap) else {
+            return;
+        };
+        self.set_selected(Some(i));
+        self.navigated = true;
+        match (beyond, wrap) {
+            (true, true) => self.emit(ListEvent::Wrapped(i)),
+            (true, false) => self.emit(ListEvent::HitEnd),
+            _ => {}
+        }
+    }
+
+    /// Moves the selection up by the given number of elements. This is
+    /// the same as calling [`Self::previous`] `n` times. If circular is true,
+    /// the selection wraps around, otherwise it stops at the first element.
+    pub fn previous_by(&mut self, n: usize) {
+        self.move_up(n, self.wrap_policy.step);
+    }
+
+    /// Selects the first element of the list, e.g. if home is pressed.
+    /// Hidden elements are skipped. Does nothing if the list is empty.
+    pub fn first(&mut self) {
+        self.select_end(false);
+    }
+
+    /// Selects the last element of the list, e.g. if end is pressed. The
+    /// list scrolls the element into view like after [`Self::next`], i.e.
+    /// it is shown at the bottom of the viewport. Hidden elements are
+    /// skipped. Does nothing if the list is empty.
+    pub fn last(&mut self) {
+        self.select_end(true);
+    }
+
+    /// Selects the first or the last element that is not hidden.
+    fn select_end(&mut self, last: bool) {
+        if self.num_elements == 0 || self.navigation_disabled {
+            return;
+        }
+        let index = if last { self.num_elements - 1 } else { 0 };
+        let Some(index) = self.skip_hidden(index, !last, false) else {
+            return;
+        };
+        self.set_selected(Some(index));
+        self.navigated = true;
+    }
+
+    /// Moves the selection up by the given number of elements and
+    /// wraps around if `wrap` is true.
+    fn move_up(&mut self, n: usize, wrap: bool) {
+        if self.num_elements == 0 || n == 0 || self.navigation_disabled {
+            return;
+        }
+        let last = self.num_elements - 1;
+        // If none is selected, the first step selects the last element
+        let (i, n) = match self.selected() {
+            Some(i) => (i.min(last), n),
+            None => (last, n - 1),
+        };
+        let beyond = n > i;
+        let i = if !wrap {
+            i.saturating_sub(n)
+        } else {
+            (i + self.num_elements - n % self.num_elements) % self.num_elements
+        };
+        let Some(i) = self.skip_hidden(i, false, wrap) else {
+            return;
+        };
+        self.set_selected(Some(i));
+        self.navigated = true;
+        match (beyond, wrap) {
+            (true, true) => self.emit(ListEvent::Wrapped(i)),
+            (true, false) => self.emit(ListEvent::HitEnd),
+            _ => {}
+        }
+    }
+
+    /// Returns the first item from the given index onwards in the direction
+    /// of the move that is not hidden, see `Listable::height`. If there is
+    /// none before the end of the list and the move does not wrap, the
+    /// nearest item that is not hidden in the opposite direction is returned.
+    fn skip_hidden(&self, index: usize, down: bool, wrap: bool) -> Option<usize> {
+        let len = self.num_elements;
+        let step = |i: usize, down: bool| match (down, wrap) {
+            (true, true) => Some((i + 1) % len),
+            (false, true) => Some((i + len - 1) % len),
+            (true, false) => Some(i + 1).filter(|&i| i < len),
+            (false, false) => i.checked_sub(1),
+        };
+        let find = |down: bool| {
+            let mut i = Some(index);
+            for _ in 0..len {
+                let index = i?;
+                if !self.hidden.contains(&index) {
+                    return Some(index);
                 }
+                i = step(index, down);
             }
-            None => 0,
+            None
         };
-        self.select(Some(i));
+        find(down).or_else(|| find(!down))
     }
 
-    /// Selects the previous element of the list. If circular is true,
-    /// calling previous on the first element selects the last.
-    pub fn previous(&mut self) {
-        if self.num_elements == 0 {
+    /// Moves the selection by a signed number of elements. Positive values
+    /// move the selection down, negative values move it up. See
+    /// [`Self::next_by`] and [`Self::previous_by`].
+    pub fn select_relative(&mut self, delta: isize) {
+        if delta >= 0 {
+            self.next_by(delta.unsigned_abs());
+        } else {
+            self.previous_by(delta.unsigned_abs());
+        }
+    }
+
+    /// Moves the selection down by the number of items that were fully
+    /// visible in the last render, but at least by one item. Unlike a
+    /// page of rows, this moves by the same number of items in lists with
+    /// variable item heights. Returns the new selection.
+    pub fn next_visible_page(&mut self) -> Option<usize> {
+        let previous = self.selected;
+        self.move_down(self.visible_items.max(1), self.wrap_policy.page);
+        if let Some(index) = self.selected.filter(|_| self.selected != previous) {
+            self.emit(ListEvent::Paged(index));
+        }
+        self.selected
+    }
+
+    /// Moves the selection up by the number of items that were fully
+    /// visible in the last render, but at least by one item. Returns
+    /// the new selection.
+    pub fn previous_visible_page(&mut self) -> Option<usize> {
+        let previous = self.selected;
+        self.move_up(self.visible_items.max(1), self.wrap_policy.page);
+        if let Some(index) = self.selected.filter(|_| self.selected != previous) {
+            self.emit(ListEvent::Paged(index));
+        }
+        self.selected
+    }
+
+    /// Scrolls the viewport down by one page, such that the item at the
+    /// bottom of the viewport in the last render moves to the top, and moves
+    /// the selection by the same number of items, but at least by one. This
+    /// takes the heights of the items into account. Stops at the last item.
+    /// If paging wraps, see [`WrapPolicy::page`], paging down from the last
+    /// item selects the first item.
+    pub fn page_down(&mut self) {
+        self.page(true);
+    }
+
+    /// Scrolls the viewport up by one page, such that the item at the top
+    /// of the viewport in the last render moves to the bottom, and moves
+    /// the selection by the same number of items, see [`Self::page_down`].
+    /// Stops at the first item. If paging wraps, see [`WrapPolicy::page`],
+    /// paging up from the first item selects the last item.
+    pub fn page_up(&mut self) {
+        self.page(false);
+    }
+
+    /// Moves the offset and the selection by one page.
+    fn page(&mut self, down: bool) {
+        if self.num_elements == 0 || self.navigation_disabled {
+            return;
+        }
+        // Paging stops at the end of the list and wraps only from there
+        let end = if down {
+            self.skip_hidden(self.num_elements - 1, false, false)
+        } else {
+            self.skip_hidden(0, true, false)
+        };
+        if self.wrap_policy.page && self.selected.is_some() && self.selected == end {
+            self.select_end(!down);
+            if let Some(index) = self.selected.filter(|_| self.selected != end) {
+                self.emit(ListEvent::Wrapped(index));
+                self.emit(ListEvent::Paged(index));
+            }
+            return;
+        }
+
+        // The offset is a position in the layout, which is reversed if the
+        // items are laid out in reverse order.
+        let forward = down != self.reversed;
+        let previous = (self.selected, self.offset, self.top_clip);
+        let offset = self.offset;
+        let delta = self.page_offset(forward).abs_diff(offset).max(1);
+        if down {
+            self.move_down(delta, false);
+        } else {
+            self.move_up(delta, false);
+        }
+        let max_offset = self.viewport_length.map_or(offset, |height| {
+            Self::max_offset(&self.heights, height as usize, false)
+        });
+        self.offset = if forward {
+            (offset + delta).min(max_offset)
+        } else {
+            offset.saturating_sub(delta)
+        };
+        self.top_clip = 0;
+        let moved = (self.selected, self.offset, self.top_clip) != previous;
+        if let Some(index) = self.selected.filter(|_| moved) {
+            self.emit(ListEvent::Paged(index));
+        }
+    }
+
+    /// Returns the offset one page ahead or back in the layout of the last
+    /// render. Ahead, the item at the bottom of the viewport is at the top,
+    /// back, the item at the top of the viewport is at the bottom.
+    fn page_offset(&self, forward: bool) -> usize {
+        let Some(height) = self.viewport_length.map(usize::from) else {
+            return self.offset;
+        };
+        if self.offset >= self.heights.len() {
+            return self.offset;
+        }
+        if forward {
+            return self.offset + self.visible_range.len().saturating_sub(1);
+        }
+        let mut offset = self.offset;
+        let mut rows = self.heights[offset];
+        while offset > 0 && rows + self.heights[offset - 1] <= height {
+            rows += self.heights[offset - 1];
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Updates the offset such that the selected item is shown on the viewport,
+    /// following the scroll strategy of the last render, e.g. the selection is
+    /// centered with [`ScrollStrategy::Center`]. The programmatic scroll
+    /// strategy applies unless the selection was last changed by navigating,
+    /// see `List::programmatic_scroll_strategy`. The list does this on every
+    /// render. Calling it explicitly is useful if the offset is needed before
+    /// the next render, e.g. after [`Self::select`]. Has no effect before the
+    /// list was rendered once.
+    pub fn scroll_to_selection(&mut self) {
+        self.detached = false;
+        self.reveal(self.selected, self.active_scroll_strategy());
+    }
+
+    /// Updates the offset such that the item with the given index is shown
+    /// on the viewport, or the first item if none, based on the heights of
+    /// the last render. Returns false and leaves the offset unchanged if the
+    /// item is not one of the items that scroll, e.g. a pinned item.
+    fn reveal(&mut self, index: Option<usize>, strategy: ScrollStrategy) -> bool {
+        // The heights and the offset count only the items below the pinned items
+        let position = match index {
+            Some(index) => match index
+                .checked_sub(self.sticky_header)
+                .filter(|&i| i < self.heights.len())
+            {
+                Some(i) => Some(i),
+                None => return false,
+            },
+            None => None,
+        };
+        let selected = std::mem::replace(&mut self.selected, position);
+        let heights = std::mem::take(&mut self.heights);
+        self.update_view_port(&heights, self.max_height(), self.truncate, strategy);
+        self.heights = heights;
+        self.selected = selected;
+        true
+    }
+
+    /// Returns how the viewport follows the selection, depending on whether
+    /// the selection was last changed by navigating.
+    pub(crate) fn active_scroll_strategy(&self) -> ScrollStrategy {
+        if self.navigated {
+            self.scroll_strategy
+        } else {
+            self.programmatic_scroll_strategy
+        }
+    }
+
+    /// Scrolls the viewport such that the item with the given index is fully
+    /// shown, following the programmatic scroll strategy of the last render,
+    /// see `List::programmatic_scroll_strategy`, without changing the
+    /// selection, e.g. to peek at an item. Like [`Self::scroll_down`], the
+    /// offset no longer follows the selection until the selection changes,
+    /// so a subsequent [`Self::next`] scrolls the selection back into view.
+    /// Based on the heights of the last render, so it has no effect before
+    /// the list was rendered once.
+    pub fn scroll_to(&mut self, index: usize) {
+        if !self.reveal(Some(index), self.programmatic_scroll_strategy) {
             return;
         }
-        let i = match self.selected() {
-            Some(i) => {
-                if i == 0 {
-                    if self.non_circular {
-                        i
-                    } else {
-                        self.num_elements - 1
-                    }
-                } else {
-                    i - 1
-                }
+        self.scrolling = false;
+        self.detached = true;
+        self.emit(ListEvent::Scrolled(self.offset));
+    }
+
+    /// Scrolls the viewport such that the selected item is shown at the
+    /// top, or the first item if none is selected, without changing the
+    /// selection. E.g. a detail list scrolls back to the top if the
+    /// selection of its master list changes.
+    pub fn reset_scroll_keep_selection(&mut self) {
+        let len = self.num_elements;
+        self.offset = self
+            .selected
+            .filter(|&index| index < len)
+            .map_or(0, |index| self.layout_position(index, len));
+        self.top_clip = 0;
+        self.scrolling = false;
+        self.detached = false;
+    }
+
+    /// Scrolls the viewport down by the given number of items without
+    /// changing the selection. The offset no longer follows the selection
+    /// until the selection changes. The list clamps the offset on render.
+    pub fn scroll_down(&mut self, items: usize) {
+        self.offset = self.offset.saturating_add(items);
+        self.top_clip = 0;
+        self.scrolling = false;
+        self.detached = true;
+        self.emit(ListEvent::Scrolled(self.offset));
+    }
+
+    /// Scrolls the viewport up by the given number of items without
+    /// changing the selection. The offset no longer follows the selection
+    /// until the selection changes.
+    pub fn scroll_up(&mut self, items: usize) {
+        self.offset = self.offset.saturating_sub(items);
+        self.top_clip = 0;
+        self.scrolling = false;
+        self.detached = true;
+        self.emit(ListEvent::Scrolled(self.offset));
+    }
+
+    /// Returns the number of columns by which the items are scrolled
+    /// horizontally. It is passed to the items in `ItemContext::h_offset`.
+    #[must_use]
+    pub fn h_offset(&self) -> u16 {
+        self.h_offset
+    }
+
+    /// Scrolls the content of all items to the right by the given number
+    /// of columns, i.e. the items show columns further right, e.g. of a
+    /// table that is wider than the list. The items shift their content
+    /// by `ItemContext::h_offset`. The offset is not clamped, as the width
+    /// of the content is only known to the items.
+    pub fn scroll_right(&mut self, columns: u16) {
+        self.h_offset = self.h_offset.saturating_add(columns);
+    }
+
+    /// Scrolls the content of all items back to the left by the given
+    /// number of columns, see [`Self::scroll_right`].
+    pub fn scroll_left(&mut self, columns: u16) {
+        self.h_offset = self.h_offset.saturating_sub(columns);
+    }
+
+    /// Scrolls the viewport by the given number of rows without changing
+    /// the selection, e.g. for a drag gesture. Positive values scroll down,
+    /// negative values scroll up. The first item on the screen may be cut
+    /// off by any number of rows. Like [`Self::scroll_down`], the offset no
+    /// longer follows the selection until the selection changes. The rows
+    /// are clamped to the content of the last render. Returns the number
+    /// of rows that are scrolled off the top, see [`Self::scroll_offset_rows`].
+    pub fn apply_drag(&mut self, delta_rows: i32) -> usize {
+        let rows = self
+            .scroll_offset_rows()
+            .saturating_add_signed(delta_rows as isize);
+        self.scroll_to_row(rows)
+    }
+
+    /// Scrolls the viewport so that the given number of content rows are
+    /// scrolled off the top, without changing the selection. The first
+    /// item on the screen is cut off by the remaining rows, which are
+    /// passed to `Listable::truncate_top` on render. The row is clamped to
+    /// the content of the last render. Returns the clamped row, see
+    /// [`Self::scroll_offset_rows`].
+    pub fn scroll_to_row(&mut self, row: usize) -> usize {
+        let total: usize = self.heights.iter().sum();
+        let row = row.min(total.saturating_sub(self.max_height()));
+
+        // Find the item that is cut off at the row
+        let (mut offset, mut above) = (0, 0);
+        while offset + 1 < self.heights.len() && above + self.heights[offset] <= row {
+            above += self.heights[offset];
+            offset += 1;
+        }
+        self.offset = offset;
+        self.top_clip = row - above;
+        let first_height = self.heights.get(offset).copied().unwrap_or_default();
+        self.clamp_top_clip(first_height, usize::MAX);
+        let row = above + self.top_clip;
+        self.scrolling = false;
+        self.detached = true;
+        self.emit(ListEvent::Scrolled(self.offset));
+        row
+    }
+
+    /// Clamps the rows that are cut off the first item on the screen to
+    /// the given maximum. The first item keeps at least one row on the
+    /// screen. If the clip does not fit the height of the first item
+    /// anymore, e.g. because the item shrank since the last render, it
+    /// is reset.
+    pub(crate) fn clamp_top_clip(&mut self, first_height: usize, max_clip: usize) {
+        if self.top_clip >= first_height {
+            self.top_clip = 0;
+        }
+        self.top_clip = self.top_clip.min(max_clip);
+    }
+
+    /// Returns the height of the viewport from the last render, i.e. the
+    /// height of the area inside the block of the list. This is none
+    /// before the list was rendered once.
+    #[must_use]
+    pub fn viewport_height(&self) -> Option<u16> {
+        self.viewport_height
+    }
+
+    /// Returns statistics about the last render, e.g. how many items were
+    /// measured and rendered. Only available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn last_render_stats(&self) -> crate::RenderStats {
+        self.render_stats
+    }
+
+    /// Returns the length of the viewport from the last render or zero.
+    pub(crate) fn max_height(&self) -> usize {
+        self.viewport_length.map_or(0, usize::from)
+    }
+
+    /// Returns the indices of the items that were shown on the viewport
+    /// in the last render, including the items that are partially
+    /// visible at the edges. This is empty before the list was rendered.
+    #[must_use]
+    pub fn visible_range(&self) -> Range<usize> {
+        self.visible_range.clone()
+    }
+
+    /// Returns the indices of the items of which at least the given
+    /// fraction of rows was shown on the viewport in the last render,
+    /// e.g. to mark the items of a feed as read once they were seen.
+    /// A threshold of one returns only the fully visible items. The
+    /// rows of a separator count as rows of the item above it.
+    #[must_use]
+    pub fn items_seen(&self, threshold: f32) -> Vec<usize> {
+        self.layout
+            .iter()
+            .filter(|item| {
+                let shown = item.rect.height as usize;
+                let height = shown + item.truncated_top + item.truncated_bottom;
+                height > 0 && shown as f32 / height as f32 >= threshold
+            })
+            .map(|item| item.index)
+            .collect()
+    }
+
+    /// Returns the layout of the items that were shown in the last render,
+    /// in the order in which they were laid out, e.g. to align overlays
+    /// with the items.
+    #[must_use]
+    pub fn layout(&self) -> &[ItemLayout] {
+        &self.layout
+    }
+
+    /// Returns the element of the list at the given position on the
+    /// screen, based on the last render. Scroll indicators take precedence
+    /// over the items beneath them, see `List::scroll_indicators`.
+    #[must_use]
+    pub fn element_at(&self, x: u16, y: u16) -> Option<ListElement> {
+        if let Some(bar) = self.scrollbar {
+            if x == bar.x && (bar.top()..bar.bottom()).contains(&y) {
+                return Some(self.scrollbar_element(bar, y));
+            }
+        }
+        match self.indicators {
+            [Some((ix, iy, _)), _] if (ix, iy) == (x, y) => Some(ListElement::TopIndicator),
+            [_, Some((ix, iy, _))] if (ix, iy) == (x, y) => Some(ListElement::BottomIndicator),
+            _ => self
+                .layout
+                .iter()
+                .find(|item| {
+                    let area = item.rect;
+                    (area.left()..area.right()).contains(&x)
+                        && (area.top()..area.bottom()).contains(&y)
+                })
+                .map(|item| ListElement::Item(item.index)),
+        }
+    }
+
+    /// Returns the part of the scrollbar at the given row. The row is mapped
+    /// to the rows of the items like the thumb, i.e. it is on the thumb if
+    /// it maps to a row on the viewport.
+    fn scrollbar_element(&self, bar: Rect, y: u16) -> ListElement {
+        let content: usize = self.heights.iter().sum();
+        let viewport = self.viewport_length.unwrap_or(bar.height) as usize;
+        let row = (y - bar.y) as usize * content / bar.height.max(1) as usize;
+        let top = self
+            .scroll_offset_rows()
+            .min(content.saturating_sub(viewport));
+        if row < top {
+            ListElement::TrackAbove
+        } else if row >= top + viewport {
+            ListElement::TrackBelow
+        } else {
+            ListElement::Thumb
+        }
+    }
+
+    /// Returns the index of the item at the given position on the screen,
+    /// based on the last render.
+    #[must_use]
+    pub fn item_at(&self, x: u16, y: u16) -> Option<usize> {
+        match self.element_at(x, y) {
+            Some(ListElement::Item(index)) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Handles a click at the given position on the screen. A click on an
+    /// item selects it, a click on a scroll indicator selects the item at
+    /// the respective end of the list. A click on the track of the scrollbar
+    /// scrolls by the number of items that were fully visible, without
+    /// changing the selection. Returns the clicked element.
+    pub fn click(&mut self, x: u16, y: u16) -> Option<ListElement> {
+        let element = self.element_at(x, y)?;
+        let index = match element {
+            ListElement::TopIndicator => self.indicators[0].map(|(_, _, index)| index),
+            ListElement::BottomIndicator => self.indicators[1].map(|(_, _, index)| index),
+            ListElement::Item(index) => Some(index),
+            ListElement::TrackAbove => {
+                self.scroll_up(self.visible_items.max(1));
+                return Some(element);
+            }
+            ListElement::TrackBelow => {
+                self.scroll_down(self.visible_items.max(1));
+                return Some(element);
+            }
+            ListElement::Thumb => return Some(element),
+        };
+        if !self.navigation_disabled {
+            self.set_selected(index);
+            self.navigated = true;
+        }
+        Some(element)
+    }
+
+    /// Returns the number of content rows that are scrolled off the top of
+    /// the viewport, i.e. the heights of the items above the first item on
+    /// the screen plus the truncated rows of the first item. Based on the
+    /// last render. Useful to position the thumb of a scrollbar.
+    #[must_use]
+    pub fn scroll_offset_rows(&self) -> usize {
+        let above: usize = self.heights.iter().take(self.offset).sum();
+        above + self.top_clip
+    }
+
+    /// Returns the state of a scrollbar for the last render, e.g. to render
+    /// a scrollbar next to the list instead of `List::scrollbar`. The content
+    /// length is the sum of the heights of the items, and the viewport length
+    /// is the height of the viewport. The position is the number of rows above
+    /// the viewport, see [`Self::scroll_offset_rows`], scaled to the content
+    /// length, so that the thumb reaches the end of the track once the items
+    /// are scrolled to the end.
+    #[must_use]
+    pub fn scrollbar_state(&self) -> ScrollbarState {
+        let content: usize = self.heights.iter().sum();
+        let viewport = self.viewport_length.unwrap_or_default() as usize;
+        let max_scroll = content.saturating_sub(viewport);
+        let position = match max_scroll {
+            0 => 0,
+            _ => self.scroll_offset_rows().min(max_scroll) * content / max_scroll,
+        };
+        ScrollbarState::new(content)
+            .position(position)
+            .viewport_content_length(viewport)
+    }
+
+    /// Whether there are items hidden above the viewport. Based on
+    /// the last render.
+    #[must_use]
+    pub fn can_scroll_up(&self) -> bool {
+        self.offset > 0 || self.top_clip > 0
+    }
+
+    /// Whether there are items hidden below the viewport. Based on
+    /// the last render.
+    #[must_use]
+    pub fn can_scroll_down(&self) -> bool {
+        let content_height: usize = self.heights.iter().skip(self.offset).sum();
+        content_height - self.top_clip.min(content_height) > self.max_height()
+    }
+
+    /// Moves the selection, the marked items and the animations of the
+    /// items to the new indices of the items after they were reordered.
+    pub(crate) fn reindex(&mut self, new_index: impl Fn(usize) -> usize) {
+        self.selected = self.selected.map(&new_index);
+        self.selected_items = self.selected_items.iter().map(|&i| new_index(i)).collect();
+        self.transitions = self
+            .transitions
+            .iter()
+            .map(|(&i, &transition)| (new_index(i), transition))
+            .collect();
+    }
+
+    /// Returns the position of the item with the given index in the
+    /// layout, which starts from the last item if the list is reversed.
+    pub(crate) fn layout_position(&self, index: usize, len: usize) -> usize {
+        if self.reversed {
+            len.saturating_sub(1).saturating_sub(index)
+        } else {
+            index
+        }
+    }
+
+    /// Decreases the offset as long as the items from the offset onwards
+    /// do not fill the viewport. This is used if the viewport height grew
+    /// since the last render, so that items above the viewport are revealed
+    /// instead of leaving blank rows below the last item.
+    pub(crate) fn fill_view_port(&mut self, heights: &[usize], max_height: usize) {
+        let mut content_height: usize = heights.iter().skip(self.offset).sum();
+        while self.offset > 0 {
+            let height = heights.get(self.offset - 1).copied().unwrap_or(0);
+            if content_height + height > max_height {
+                break;
+            }
+            content_height += height;
+            self.offset -= 1;
+        }
+    }
+
+    /// Moves the offset from the previous offset by one item towards the
+    /// current offset if the list is scrolling. Returns the new offset,
+    /// or none if the offset is not changed.
+    pub(crate) fn scroll_step(&mut self, previous_offset: usize) -> Option<usize> {
+        if !self.scrolling {
+            return None;
+        }
+        let target = self.offset;
+        let offset = match previous_offset.cmp(&target) {
+            std::cmp::Ordering::Less => previous_offset + 1,
+            std::cmp::Ordering::Greater => previous_offset - 1,
+            std::cmp::Ordering::Equal => target,
+        };
+        self.scrolling = offset != target;
+        self.offset = offset;
+        self.top_clip = 0;
+        Some(offset)
+    }
+
+    /// Returns the largest offset for the given heights. If `overscroll`
+    /// is false, this is the offset at which the last item touches the
+    /// bottom of the viewport, otherwise the index of the last item.
+    pub(crate) fn max_offset(heights: &[usize], max_height: usize, overscroll: bool) -> usize {
+        let last = heights.len().saturating_sub(1);
+        if overscroll {
+            return last;
+        }
+        let mut state = Self {
+            offset: heights.len(),
+            ..Self::default()
+        };
+        state.fill_view_port(heights, max_height);
+        state.offset.min(last)
+    }
+
+    /// Returns the heights of the items on the viewport if the given
+    /// item is the first item on the screen.
+    pub(crate) fn view_heights(
+        heights: &[usize],
+        offset: usize,
+        max_height: usize,
+        truncate: bool,
+    ) -> Vec<usize> {
+        let mut view_heights: Vec<usize> = Vec::new();
+        let mut y = 0;
+        for height in heights.iter().skip(offset) {
+            // Out of bounds
+            if y + height > max_height {
+                // Truncate the last widget. The first widget is always
+                // shown, even if it is larger than the viewport.
+                if truncate || view_heights.is_empty() {
+                    view_heights.push(max_height - y);
+                }
+                break;
+            }
+            y += height;
+            view_heights.push(*height);
+        }
+        view_heights
+    }
+
+    /// Moves the offset such that the selected item has at least the given
+    /// number of rows of other items above and below it on the viewport, as
+    /// far as there are items before and after it. Takes the heights of the
+    /// items on the viewport after [`Self::update_view_port`] and returns
+    /// them for the new offset.
+    pub(crate) fn apply_scroll_padding(
+        &mut self,
+        heights: &[usize],
+        max_height: usize,
+        truncate: Truncate,
+        padding: usize,
+        mut view_heights: Vec<usize>,
+    ) -> Vec<usize> {
+        let Some(index) = self.selected.filter(|_| padding > 0) else {
+            return view_heights;
+        };
+        let selected = self.layout_position(index, heights.len());
+        if selected < self.offset || selected >= heights.len() {
+            return view_heights;
+        }
+        let rows_above = |offset: usize| heights[offset..selected].iter().sum::<usize>();
+
+        // Reveal the items above the selection, as long as the selection fits
+        let wanted_above = padding.min(rows_above(0));
+        if rows_above(self.offset).saturating_sub(self.top_clip) < wanted_above {
+            self.top_clip = 0;
+            while self.offset > 0
+                && rows_above(self.offset) < wanted_above
+                && rows_above(self.offset - 1) + heights[selected] <= max_height
+            {
+                self.offset -= 1;
             }
-            None => 0,
+            view_heights = Self::view_heights(heights, self.offset, max_height, truncate.bottom);
+        }
+
+        // Reveal the items below the selection by scrolling down
+        let wanted_below = padding.min(heights[selected + 1..].iter().sum());
+        let rows_below = |offset: usize, view_heights: &[usize]| -> usize {
+            view_heights.iter().skip(selected + 1 - offset).sum()
         };
-        self.select(Some(i));
+        while self.offset < selected && rows_below(self.offset, &view_heights) < wanted_below {
+            self.offset += 1;
+            self.top_clip = 0;
+            view_heights = Self::view_heights(heights, self.offset, max_height, truncate.bottom);
+        }
+        view_heights
     }
 
     /// Here we check and if necessary update the viewport. For this we start with the first item
@@ -101,13 +1369,43 @@ impl ListState {
         &mut self,
         heights: &[usize],
         max_height: usize,
-        truncate: bool,
+        truncate: Truncate,
+        strategy: ScrollStrategy,
     ) -> Vec<usize> {
-        // The items heights on the viewport will be calculated on the fly.
-        let mut view_heights: Vec<usize> = Vec::new();
-
         // If none is selected, the first item should be show on top of the viewport.
-        let selected = self.selected.unwrap_or(0);
+        let selected = self
+            .selected
+            .map_or(0, |index| self.layout_position(index, heights.len()));
+        self.top_clip = 0;
+
+        // Center the selected item by filling the rows above it with the
+        // items before it, but do not leave blank rows below the last item.
+        // The centered strategy does so only if the item is not fully shown.
+        let center = match strategy {
+            ScrollStrategy::Edge => false,
+            ScrollStrategy::Center => {
+                let view_heights =
+                    Self::view_heights(heights, self.offset, max_height, truncate.bottom);
+                let fully_visible = view_heights
+                    .iter()
+                    .zip(heights.iter().skip(self.offset))
+                    .take_while(|(view_height, height)| view_height == height)
+                    .count();
+                !(self.offset..self.offset + fully_visible).contains(&selected)
+            }
+            ScrollStrategy::AlwaysCenter => true,
+        };
+        if center && self.selected.is_some() {
+            let mut above = max_height.saturating_sub(heights[selected]) / 2;
+            self.offset = selected;
+            while self.offset > 0 && heights[self.offset - 1] <= above {
+                above -= heights[self.offset - 1];
+                self.offset -= 1;
+            }
+            self.offset = self
+                .offset
+                .min(Self::max_offset(heights, max_height, false));
+        }
 
         // If the selected value is smaller than the offset, we roll
         // the offset so that the selected value is at the top
@@ -116,27 +1414,14 @@ impl ListState {
         }
 
         // Check if the selected item is in the current view
-        let (mut y, mut i) = (0, self.offset);
-        let mut found = false;
-        for height in heights.iter().skip(self.offset) {
-            // Out of bounds
-            if y + height > max_height {
-                if truncate {
-                    // Truncate the last widget
-                    view_heights.push(max_height - y);
-                }
-                break;
-            }
-            // Selected value is within view/bounds, so we are good
-            // but we keep iterating to collect the view heights
-            if selected == i {
-                found = true;
-            }
-            y += height;
-            i += 1;
-            view_heights.push(*height);
-        }
-        if found {
+        let mut view_heights =
+            Self::view_heights(heights, self.offset, max_height, truncate.bottom);
+        let fully_visible = view_heights
+            .iter()
+            .zip(heights.iter().skip(self.offset))
+            .take_while(|(view_height, height)| view_height == height)
+            .count();
+        if (self.offset..self.offset + fully_visible).contains(&selected) {
             return view_heights;
         }
 
@@ -148,13 +1433,16 @@ impl ListState {
         for height in heights.iter().rev().skip(last.saturating_sub(selected)) {
             // out of bounds
             if y + height >= max_height {
-                if truncate {
-                    // Truncate the first widget.
-                    // At the moment this will truncate the bottom of the first item, which
-                    // looks a bit strange, but I have not figured out how to truncate a
-                    // widget from the top.
+                // The selected widget is always shown, even if it is larger than
+                // the viewport, and a widget that fits exactly is never cut off.
+                if truncate.top || y + height == max_height || i == selected {
+                    // Truncate the first widget. The rows that are cut off
+                    // are passed to `Listable::truncate_top` on render.
                     view_heights.insert(0, max_height - y);
                     self.offset = i;
+                    if i != selected {
+                        self.top_clip = y + height - max_height;
+                    }
                 } else {
                     self.offset = i + 1;
                 }
@@ -164,6 +1452,13 @@ impl ListState {
             y += height;
             i -= 1;
         }
+
+        // If the first widget is not truncated, the offset is aligned to its top
+        // and the rows below the selected widget are filled with the next widgets.
+        if !truncate.top {
+            self.top_clip = 0;
+            view_heights = Self::view_heights(heights, self.offset, max_height, truncate.bottom);
+        }
         view_heights
     }
 }
@@ -192,11 +1487,11 @@ mod tests {
                     offset: $given_offset,
                     selected: $given_selected,
                     num_elements: $given_heights.len(),
-                    non_circular: false,
+                    ..ListState::default()
                 };
 
                 //when
-                let heights = given_state.update_view_port(&$given_heights, $given_max_height, true);
+                let heights = given_state.update_view_port(&$given_heights, $given_max_height, Truncate::default(), ScrollStrategy::Edge);
                 let offset = given_state.offset;
 
                 // then
@@ -209,11 +1504,884 @@ mod tests {
     }
 
     update_view_port_tests! {
-        happy_path: [0, Some(0), vec![2, 3], 6], [0, vec![2, 3]],
-        empty_list: [0, None, Vec::<usize>::new(), 4], [0, vec![]],
-        update_offset_down: [0, Some(2), vec![2, 3, 3], 6], [1, vec![3, 3]],
-        update_offset_up: [1, Some(0), vec![2, 3, 3], 6], [0, vec![2, 3, 1]],
-        truncate_bottom: [0, Some(0), vec![2, 3], 4], [0, vec![2, 2]],
-        truncate_top: [0, Some(1), vec![2, 3], 4], [0, vec![1, 3]],
+        happy_path: [0, Some(0), [2, 3], 6], [0, vec![2, 3]],
+        empty_list: [0, None, [0; 0], 4], [0, vec![]],
+        update_offset_down: [0, Some(2), [2, 3, 3], 6], [1, vec![3, 3]],
+        update_offset_up: [1, Some(0), [2, 3, 3], 6], [0, vec![2, 3, 1]],
+        truncate_bottom: [0, Some(0), [2, 3], 4], [0, vec![2, 2]],
+        truncate_top: [0, Some(1), [2, 3], 4], [0, vec![1, 3]],
+    }
+
+    #[test]
+    fn scroll_to_selection() {
+        // given
+        let mut state = ListState {
+            heights: vec![2, 3, 3],
+            viewport_length: Some(6),
+            truncate: Truncate::default(),
+            ..ListState::default()
+        };
+        state.select(Some(2));
+
+        // when
+        state.scroll_to_selection();
+
+        // then
+        assert_eq!(state.offset, 1);
+    }
+
+    #[test]
+    fn scroll_to_selection_centered() {
+        // given
+        let mut state = ListState {
+            selected: Some(5),
+            navigated: true,
+            heights: vec![1; 10],
+            viewport_length: Some(3),
+            scroll_strategy: ScrollStrategy::Center,
+            ..ListState::default()
+        };
+
+        // when
+        state.scroll_to_selection();
+
+        // then
+        assert_eq!(state.offset, 4);
+    }
+
+    #[test]
+    fn programmatic_scroll_strategy() {
+        // given
+        let mut state = ListState {
+            heights: vec![1; 10],
+            viewport_length: Some(3),
+            programmatic_scroll_strategy: ScrollStrategy::Center,
+            ..ListState::default()
+        };
+
+        // when
+        state.selected = Some(5);
+        state.scroll_to_selection();
+
+        // then
+        assert_eq!(state.offset, 4);
+
+        // when
+        state.scroll_to(8);
+
+        // then
+        assert_eq!(state.offset, 7);
+    }
+
+    #[test]
+    fn select() {
+        // given
+        let mut state = ListState {
+            num_elements: 10,
+            heights: vec![1; 10],
+            viewport_length: Some(3),
+            ..ListState::default()
+        };
+
+        // when
+        state.select(Some(20));
+
+        // then
+        assert_eq!(state.selected(), Some(9));
+        assert_eq!(state.offset, 7);
+
+        // when
+        state.select(None);
+
+        // then
+        assert_eq!(state.selected(), None);
+        assert_eq!(state.offset, 0);
+    }
+
+    #[test]
+    fn scroll_to_selection_before_render() {
+        // given
+        let mut state = ListState::default();
+        state.select(Some(2));
+
+        // when
+        state.scroll_to_selection();
+
+        // then
+        assert_eq!(state.offset, 0);
+    }
+
+    #[test]
+    fn can_scroll() {
+        // given
+        let mut state = ListState {
+            heights: vec![2, 3, 3],
+            viewport_length: Some(6),
+            ..ListState::default()
+        };
+
+        // then
+        assert!(!state.can_scroll_up());
+        assert!(state.can_scroll_down());
+
+        // when
+        state.offset = 1;
+
+        // then
+        assert!(state.can_scroll_up());
+        assert!(!state.can_scroll_down());
+    }
+
+    #[test]
+    fn fill_view_port() {
+        // given
+        let mut state = ListState {
+            offset: 3,
+            ..ListState::default()
+        };
+
+        // when
+        state.fill_view_port(&[1, 2, 2, 1, 1], 5);
+
+        // then
+        assert_eq!(state.offset, 2);
+    }
+
+    #[test]
+    fn jump_to_instant() {
+        // given
+        let mut state = ListState {
+            heights: vec![1; 10],
+            viewport_length: Some(3),
+            truncate: Truncate::default(),
+            ..ListState::default()
+        };
+
+        // when
+        state.jump_to(9, JumpMode::Instant);
+
+        // then
+        assert_eq!(state.selected(), Some(9));
+        assert_eq!(state.offset, 7);
+    }
+
+    #[test]
+    fn jump_to_clamps_the_index() {
+        // given
+        let mut state = ListState {
+            num_elements: 3,
+            heights: vec![1; 3],
+            viewport_length: Some(3),
+            ..ListState::default()
+        };
+
+        // when
+        state.jump_to(5, JumpMode::Instant);
+
+        // then
+        assert_eq!(state.selected(), Some(2));
+
+        // when the navigation is disabled
+        state.navigation_disabled = true;
+        state.jump_to(0, JumpMode::Scroll);
+
+        // then
+        assert_eq!(state.selected(), Some(2));
+        assert!(!state.is_animating());
+    }
+
+    #[test]
+    fn jump_to_scroll() {
+        // given
+        let mut state = ListState {
+            heights: vec![1; 10],
+            viewport_length: Some(3),
+            truncate: Truncate::default(),
+            ..ListState::default()
+        };
+        state.jump_to(4, JumpMode::Scroll);
+
+        // when
+        let mut offsets = Vec::new();
+        while state.is_animating() {
+            let previous_offset = state.offset;
+            state.update_view_port(&[1; 10], 3, Truncate::default(), ScrollStrategy::Edge);
+            offsets.push(state.scroll_step(previous_offset));
+        }
+
+        // then
+        assert_eq!(offsets, vec![Some(1), Some(2)]);
+        assert_eq!(state.selected(), Some(4));
+    }
+
+    #[test]
+    fn max_offset() {
+        assert_eq!(ListState::max_offset(&[1, 2, 2, 1], 3, false), 2);
+        assert_eq!(ListState::max_offset(&[1, 2, 2, 1], 3, true), 3);
+        assert_eq!(ListState::max_offset(&[1, 4], 3, false), 1);
+        assert_eq!(ListState::max_offset(&[], 3, false), 0);
+    }
+
+    #[test]
+    fn scroll_detaches_offset() {
+        // given
+        let mut state = ListState::default();
+        state.set_num_elements(3);
+        state.select(Some(0));
+
+        // when
+        state.scroll_down(2);
+
+        // then
+        assert_eq!(state.offset, 2);
+        assert!(state.detached);
+        assert_eq!(state.selected(), Some(0));
+
+        // when
+        state.next();
+
+        // then
+        assert!(!state.detached);
+    }
+
+    #[test]
+    fn viewport_height_before_render() {
+        assert_eq!(ListState::default().viewport_height(), None);
+    }
+
+    #[test]
+    fn truncate_bottom_only() {
+        // given
+        let mut state = ListState {
+            selected: Some(2),
+            ..ListState::default()
+        };
+        let truncate = Truncate {
+            top: false,
+            bottom: true,
+        };
+
+        // when
+        let heights = state.update_view_port(&[2, 3, 2, 3], 6, truncate, ScrollStrategy::Edge);
+
+        // then
+        assert_eq!(state.offset, 1);
+        assert_eq!(heights, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn truncate_none_fits_exactly() {
+        // given
+        let mut state = ListState {
+            selected: Some(2),
+            ..ListState::default()
+        };
+
+        // when
+        let heights =
+            state.update_view_port(&[2, 3, 3], 6, Truncate::both(false), ScrollStrategy::Edge);
+
+        // then
+        assert_eq!(state.offset, 1);
+        assert_eq!(heights, vec![3, 3]);
+    }
+
+    #[test]
+    fn truncate_none_selected_too_large() {
+        // given
+        let mut state = ListState {
+            selected: Some(1),
+            ..ListState::default()
+        };
+
+        // when
+        let heights =
+            state.update_view_port(&[2, 8], 6, Truncate::both(false), ScrollStrategy::Edge);
+
+        // then
+        assert_eq!(state.offset, 1);
+        assert_eq!(heights, vec![6]);
+    }
+
+    #[test]
+    fn next_by() {
+        let mut state = ListState::default();
+        state.set_num_elements(5);
+
+        state.next_by(3);
+        assert_eq!(state.selected(), Some(2));
+
+        state.next_by(4);
+        assert_eq!(state.selected(), Some(1));
+
+        let mut state = state.circular(false);
+        state.next_by(10);
+        assert_eq!(state.selected(), Some(4));
+    }
+
+    #[test]
+    fn first_and_last() {
+        let mut state = ListState::default();
+        state.last();
+        assert_eq!(state.selected(), None);
+
+        state.set_num_elements(5);
+        state.hidden = HashSet::from([0, 4]);
+        state.last();
+        assert_eq!(state.selected(), Some(3));
+
+        state.first();
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn previous_by() {
+        let mut state = ListState::default();
+        state.set_num_elements(5);
+        state.select(Some(1));
+
+        state.previous_by(3);
+        assert_eq!(state.selected(), Some(3));
+
+        let mut state = state.circular(false);
+        state.previous_by(10);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn select_relative() {
+        let mut state = ListState::default().circular(false);
+        state.set_num_elements(5);
+        state.select(Some(2));
+
+        state.select_relative(2);
+        assert_eq!(state.selected(), Some(4));
+
+        state.select_relative(-3);
+        assert_eq!(state.selected(), Some(1));
+
+        state.select_relative(0);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn next_and_previous() {
+        let mut state = ListState::default();
+        state.set_num_elements(3);
+
+        state.next();
+        assert_eq!(state.selected(), Some(0));
+
+        state.previous();
+        assert_eq!(state.selected(), Some(2));
+
+        state.next();
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn navigation_skips_hidden_items() {
+        let mut state = ListState {
+            hidden: HashSet::from([1, 3]),
+            ..ListState::default()
+        };
+        state.set_num_elements(4);
+        state.select(Some(0));
+
+        state.next();
+        assert_eq!(state.selected(), Some(2));
+        state.next();
+        assert_eq!(state.selected(), Some(0));
+        state.previous();
+        assert_eq!(state.selected(), Some(2));
+
+        let mut state = state.circular(false);
+        state.next();
+        assert_eq!(state.selected(), Some(2));
+    }
+
+    #[test]
+    fn reset_scroll_keep_selection() {
+        let mut state = ListState {
+            offset: 2,
+            top_clip: 1,
+            ..ListState::default()
+        };
+        state.set_num_elements(10);
+        state.reset_scroll_keep_selection();
+        assert_eq!((state.offset, state.top_clip), (0, 0));
+
+        state.select(Some(5));
+        state.reset_scroll_keep_selection();
+        assert_eq!((state.selected(), state.offset), (Some(5), 5));
+    }
+
+    #[test]
+    fn on_len_change() {
+        let mut state = ListState::default();
+        state.select(Some(8));
+        state.on_len_change(10, 5);
+        assert_eq!(state.selected(), Some(4));
+
+        let mut state = ListState::default().selection_anchor(SelectionAnchor::KeepFromEnd);
+        state.select(Some(8));
+        state.offset = 6;
+        state.on_len_change(10, 15);
+        assert_eq!((state.selected(), state.offset), (Some(13), 11));
+        state.on_len_change(15, 3);
+        assert_eq!((state.selected(), state.offset), (Some(1), 0));
+
+        let mut state = ListState::default().selection_anchor(SelectionAnchor::KeepRatio);
+        state.select(Some(5));
+        state.on_len_change(11, 21);
+        assert_eq!(state.selected(), Some(10));
+        state.on_len_change(21, 0);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn clamp_to() {
+        let mut state = ListState {
+            offset: 6,
+            ..ListState::default()
+        };
+        state.select(Some(8));
+
+        state.clamp_to(5);
+        assert_eq!((state.selected(), state.offset), (Some(4), 4));
+
+        state.clamp_to(0);
+        assert_eq!((state.selected(), state.offset), (None, 0));
+    }
+
+    #[test]
+    fn navigation_without_selection() {
+        let mut state = ListState::default();
+        state.set_num_elements(3);
+        state.next();
+        assert_eq!(state.selected(), Some(0));
+
+        let mut state = ListState::default();
+        state.set_num_elements(3);
+        state.previous();
+        assert_eq!(state.selected(), Some(2));
+
+        let mut state = ListState::default().circular(false);
+        state.set_num_elements(5);
+        state.previous_by(2);
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn toggle_selection() {
+        let mut state = ListState::default();
+
+        state.toggle_selection(1);
+        state.toggle_selection(3);
+        state.toggle_selection(1);
+        assert_eq!(state.selected_items(), &HashSet::from([3]));
+
+        state.clear_selections();
+        assert!(state.selected_items().is_empty());
+    }
+
+    #[test]
+    fn select_nearest() {
+        let mut state = ListState {
+            num_elements: 6,
+            unselectable: HashSet::from([0, 2]),
+            ..ListState::default()
+        };
+
+        state.select_nearest(1);
+        assert_eq!(state.selected(), Some(1));
+
+        state.select_nearest(0);
+        assert_eq!(state.selected(), Some(1));
+
+        // Items 1 and 3 are equally near, the later one wins
+        state.select_nearest(2);
+        assert_eq!(state.selected(), Some(3));
+
+        state.select_nearest(10);
+        assert_eq!(state.selected(), Some(5));
+
+        state.unselectable = (0..6).collect();
+        state.select_nearest(1);
+        assert_eq!(state.selected(), Some(5));
+    }
+
+    #[test]
+    fn visible_page() {
+        let mut state = ListState {
+            num_elements: 10,
+            visible_items: 3,
+            ..ListState::default()
+        }
+        .circular(false);
+
+        assert_eq!(state.next_visible_page(), Some(2));
+        assert_eq!(state.next_visible_page(), Some(5));
+        assert_eq!(state.previous_visible_page(), Some(2));
+        assert_eq!(state.previous_visible_page(), Some(0));
+
+        state.visible_items = 0;
+        assert_eq!(state.next_visible_page(), Some(1));
+    }
+
+    #[test]
+    fn wrap_policy() {
+        let mut state = ListState {
+            num_elements: 5,
+            visible_items: 2,
+            ..ListState::default()
+        }
+        .wrap_policy(WrapPolicy {
+            page: false,
+            ..WrapPolicy::default()
+        });
+        state.select(Some(4));
+
+        state.next();
+        assert_eq!(state.selected(), Some(0));
+
+        state.previous_visible_page();
+        assert_eq!(state.selected(), Some(0));
+
+        state.previous();
+        assert_eq!(state.selected(), Some(4));
+
+        state.next_visible_page();
+        assert_eq!(state.selected(), Some(4));
+    }
+
+    #[test]
+    fn activate() {
+        let mut state = ListState::default();
+        state.activate();
+        assert_eq!(state.take_activated(), None);
+
+        state.select(Some(2));
+        state.activate();
+        state.select(Some(1));
+        assert_eq!(state.take_activated(), Some(2));
+        assert_eq!(state.take_activated(), None);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn event_sink() {
+        // given
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut state = ListState::default().wrap_policy(WrapPolicy {
+            page: false,
+            ..WrapPolicy::default()
+        });
+        state.set_num_elements(3);
+        let sink = Arc::clone(&events);
+        state.set_event_sink(move |event| sink.lock().unwrap().push(event));
+
+        // when
+        state.next();
+        state.previous();
+        state.select(Some(0));
+        state.next_visible_page();
+        state.next_visible_page();
+        state.next_visible_page();
+        state.scroll_down(1);
+
+        // then
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                ListEvent::Selected(Some(0)),
+                ListEvent::Selected(Some(2)),
+                ListEvent::Wrapped(2),
+                ListEvent::Selected(Some(0)),
+                ListEvent::Selected(Some(1)),
+                ListEvent::Paged(1),
+                ListEvent::Selected(Some(2)),
+                ListEvent::Paged(2),
+                ListEvent::HitEnd,
+                ListEvent::Scrolled(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn page_down_at_the_end() {
+        // given
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut state = ListState {
+            num_elements: 3,
+            heights: vec![1; 3],
+            viewport_length: Some(2),
+            visible_range: 1..3,
+            ..ListState::default()
+        }
+        .wrap_policy(WrapPolicy {
+            page: false,
+            ..WrapPolicy::default()
+        });
+        state.select(Some(2));
+        let sink = Arc::clone(&events);
+        state.set_event_sink(move |event| sink.lock().unwrap().push(event));
+
+        // when
+        state.page_down();
+
+        // then
+        assert_eq!((state.selected(), state.offset), (Some(2), 1));
+        assert_eq!(*events.lock().unwrap(), vec![ListEvent::HitEnd]);
+
+        // when paging wraps
+        state.wrap_policy = WrapPolicy::default();
+        state.page_down();
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                ListEvent::HitEnd,
+                ListEvent::Selected(Some(0)),
+                ListEvent::Wrapped(0),
+                ListEvent::Paged(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn scroll_to_row() {
+        let mut state = ListState {
+            num_elements: 3,
+            heights: vec![4, 4, 4],
+            viewport_length: Some(3),
+            ..ListState::default()
+        };
+
+        assert_eq!(state.scroll_to_row(6), 6);
+        assert_eq!((state.offset, state.top_clip), (1, 2));
+        assert!(state.can_scroll_up());
+        assert_eq!(state.scroll_to_row(20), 9);
+        assert_eq!((state.offset, state.top_clip), (2, 1));
+        assert_eq!(state.scroll_to_row(2), 2);
+        assert_eq!((state.offset, state.top_clip), (0, 2));
+        assert!(state.can_scroll_up());
+    }
+
+    #[test]
+    fn apply_drag() {
+        let mut state = ListState {
+            num_elements: 4,
+            heights: vec![2, 3, 1, 2],
+            viewport_length: Some(4),
+            ..ListState::default()
+        };
+
+        assert_eq!(state.apply_drag(3), 3);
+        assert_eq!((state.offset, state.top_clip), (1, 1));
+        assert_eq!(state.apply_drag(10), 4);
+        assert_eq!((state.offset, state.top_clip), (1, 2));
+        assert_eq!(state.apply_drag(-2), 2);
+        assert_eq!((state.offset, state.top_clip), (1, 0));
+        assert_eq!(state.apply_drag(-5), 0);
+        assert_eq!((state.offset, state.top_clip), (0, 0));
+        assert!(state.detached);
+    }
+
+    #[test]
+    fn apply_drag_keeps_a_row_of_the_first_item() {
+        let mut state = ListState {
+            num_elements: 1,
+            heights: vec![3],
+            viewport_length: Some(0),
+            ..ListState::default()
+        };
+
+        assert_eq!(state.apply_drag(5), 0);
+        assert_eq!((state.offset, state.top_clip), (0, 0));
+    }
+
+    #[test]
+    fn scroll_offset_rows() {
+        // given
+        let mut state = ListState {
+            selected: Some(2),
+            ..ListState::default()
+        };
+
+        // when
+        state.heights = vec![2, 3, 3];
+        state.update_view_port(
+            &state.heights.clone(),
+            5,
+            Truncate::default(),
+            ScrollStrategy::Edge,
+        );
+
+        // then
+        assert_eq!(state.offset, 1);
+        assert_eq!(state.scroll_offset_rows(), 3);
+    }
+
+    /// Navigation on empty and single-item lists.
+    mod boundaries {
+        use super::*;
+        use crate::{List, Listable};
+        use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+        fn state(num_elements: usize, circular: bool) -> ListState {
+            ListState {
+                num_elements,
+                heights: vec![1; num_elements],
+                viewport_length: Some(2),
+                ..ListState::default()
+            }
+            .circular(circular)
+        }
+
+        #[derive(Clone)]
+        struct Item(&'static str);
+
+        impl Listable for Item {
+            fn height(&self) -> usize {
+                1
+            }
+        }
+
+        impl Widget for Item {
+            fn render(self, _: Rect, _: &mut Buffer) {}
+        }
+
+        fn search(list: &List<Item>, state: &mut ListState, query: &str) -> bool {
+            list.search(state, query, |item, query| item.0 == query)
+        }
+
+        type Navigation = (&'static str, fn(&mut ListState));
+
+        /// Applies every navigation method and checks the selection after each.
+        fn navigate(state: &mut ListState, expected: Option<usize>) {
+            let methods: [Navigation; 14] = [
+                ("next", ListState::next),
+                ("previous", ListState::previous),
+                ("first", ListState::first),
+                ("last", ListState::last),
+                ("page_down", ListState::page_down),
+                ("page_up", ListState::page_up),
+                ("next_by", |s| s.next_by(3)),
+                ("previous_by", |s| s.previous_by(3)),
+                ("select_relative", |s| s.select_relative(-2)),
+                ("next_visible_page", |s| {
+                    s.next_visible_page();
+                }),
+                ("previous_visible_page", |s| {
+                    s.previous_visible_page();
+                }),
+                ("select_nearest", |s| s.select_nearest(5)),
+                ("scroll_down", |s| s.scroll_down(2)),
+                ("scroll_to_selection", ListState::scroll_to_selection),
+            ];
+            for (name, method) in methods {
+                method(state);
+                assert_eq!(state.selected(), expected, "{name}");
+            }
+        }
+
+        #[test]
+        fn empty_list() {
+            for circular in [true, false] {
+                navigate(&mut state(0, circular), None);
+            }
+        }
+
+        #[test]
+        fn single_item_list() {
+            for circular in [true, false] {
+                let mut state = state(1, circular);
+                state.next();
+                assert_eq!(state.selected(), Some(0));
+                navigate(&mut state, Some(0));
+            }
+        }
+
+        #[test]
+        fn single_item_list_with_stale_selection() {
+            let mut state = state(4, true);
+            state.select(Some(3));
+            state.set_num_elements(1);
+            state.next();
+            assert_eq!(state.selected(), Some(0));
+            state.set_num_elements(4);
+            state.select(Some(3));
+            state.set_num_elements(1);
+            state.previous();
+            assert_eq!(state.selected(), Some(0));
+        }
+
+        #[test]
+        fn end_of_list() {
+            let mut state = state(3, false);
+            state.select(Some(2));
+            let methods: [Navigation; 4] = [
+                ("next", ListState::next),
+                ("next_by", |s| s.next_by(3)),
+                ("last", ListState::last),
+                ("page_down", ListState::page_down),
+            ];
+            for (name, method) in methods {
+                method(&mut state);
+                assert_eq!(state.selected(), Some(2), "{name}");
+            }
+
+            state.select(Some(0));
+            let methods: [Navigation; 4] = [
+                ("previous", ListState::previous),
+                ("previous_by", |s| s.previous_by(3)),
+                ("first", ListState::first),
+                ("page_up", ListState::page_up),
+            ];
+            for (name, method) in methods {
+                method(&mut state);
+                assert_eq!(state.selected(), Some(0), "{name}");
+            }
+        }
+
+        #[test]
+        fn search_empty_list() {
+            for circular in [true, false] {
+                let mut state = state(0, circular);
+                let list = List::new(Vec::<Item>::new());
+                assert!(!search(&list, &mut state, "a"));
+                assert_eq!(state.selected(), None);
+            }
+        }
+
+        #[test]
+        fn search_single_item_list() {
+            for circular in [true, false] {
+                let mut state = state(1, circular);
+                let list = List::new(vec![Item("a")]);
+                assert!(search(&list, &mut state, "a"));
+                assert_eq!(state.selected(), Some(0));
+                assert!(search(&list, &mut state, "a"));
+                assert_eq!(state.selected(), Some(0));
+                assert!(!search(&list, &mut state, "b"));
+                assert_eq!(state.selected(), Some(0));
+            }
+        }
+
+        #[test]
+        fn search_at_the_end_of_the_list() {
+            let list = List::new(vec![Item("a"), Item("b"), Item("c")]);
+            let mut state = state(3, false);
+            state.select(Some(2));
+            assert!(!search(&list, &mut state, "a"));
+            assert_eq!(state.selected(), Some(2));
+
+            let mut state = state.circular(true);
+            assert!(search(&list, &mut state, "a"));
+            assert_eq!(state.selected(), Some(0));
+        }
     }
 }
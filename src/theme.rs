@@ -0,0 +1,32 @@
+use ratatui::style::Style;
+
+/// A theme that the [`List`](crate::List) passes to the selected item
+/// in [`Listable::highlight_with`](crate::Listable::highlight_with).
+/// Items decide themselves which of the styles they apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HighlightTheme {
+    /// The style of a highlighted item.
+    pub style: Style,
+
+    /// The style of a highlighted item that needs attention, e.g.
+    /// a destructive action.
+    pub alert: Style,
+}
+
+impl HighlightTheme {
+    /// Instantiate a theme with the style of a highlighted item.
+    #[must_use]
+    pub fn new(style: Style) -> Self {
+        Self {
+            style,
+            alert: Style::default(),
+        }
+    }
+
+    /// Set the style of a highlighted item that needs attention.
+    #[must_use]
+    pub fn alert(mut self, alert: Style) -> Self {
+        self.alert = alert;
+        self
+    }
+}
@@ -1,10 +1,95 @@
-use ratatui::widgets::Widget;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::Style,
+    widgets::Widget,
+};
+
+use crate::{HighlightTheme, ItemContext};
 
 /// Should be implemented on widget list items to be used in `List`.
+///
+/// The list measures the items only with their heights, e.g. with
+/// [`Listable::height`] and [`Listable::height_for_width`]. An item is
+/// rendered only if it is shown on the viewport, so rendering may have
+/// side effects, e.g. to load the content of the items that are seen.
 pub trait Listable: Widget {
-    /// Returns the height of the item.
+    /// Returns the height of the item. An item with a height of zero is
+    /// hidden, e.g. if it is filtered out. Hidden items are skipped by the
+    /// navigation and cannot be selected, unless the list sets a default
+    /// or a minimum height for them.
     fn height(&self) -> usize;
 
+    /// Returns the height of the item if its content is wrapped to the given
+    /// width. Used instead of [`Listable::height`] if the list wraps its items.
+    /// Optional, returns the height by default.
+    fn height_for_width(&self, _width: u16) -> usize {
+        self.height()
+    }
+
+    /// Returns a hash of the content that determines the height of the item
+    /// if it is wrapped. If some, the list keeps the height from
+    /// [`Listable::height_for_width`] in the state and measures the item
+    /// again only if the hash or the width changes. Optional, none by default.
+    fn content_hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// The style that is set on the area of the widget before it is
+    /// rendered, on top of the base style of the list. Optional.
+    fn item_style(&self) -> Style {
+        Style::default()
+    }
+
+    /// Returns the number of rows of the item that are filled, i.e. that
+    /// take the style of the item and into which the item is rendered.
+    /// The remaining rows are left as a gap, e.g. as the margin of a card,
+    /// and take the gap style of the list. The height still decides the
+    /// layout. Optional, none by default, i.e. all rows are filled.
+    fn fill_height(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the widget paints every cell of its area. The base style
+    /// of the list is not applied beneath opaque widgets. Optional, false
+    /// by default.
+    fn opaque(&self) -> bool {
+        false
+    }
+
+    /// Returns the height of the widget in the compact form, see
+    /// [`Listable::compact`]. Optional, one by default.
+    fn compact_height(&self) -> usize {
+        1
+    }
+
+    /// Turns the widget into its compact form, e.g. a single row, if the
+    /// list is compact. Applied on render before the widget is highlighted.
+    /// Optional, returns the widget unchanged by default.
+    #[must_use]
+    fn compact(self) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Returns the width of the item if the list lays out its items from
+    /// left to right, see `List::direction`. Items with a width of zero are
+    /// hidden. Items of a horizontal list should implement it. Optional,
+    /// none by default, i.e. the item takes the full width of the viewport.
+    fn width(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the height of the widget once it is highlighted. Used
+    /// instead of [`Listable::height`] for the selected widget, so that
+    /// the widget is only highlighted if it is shown. Must be implemented
+    /// if highlighting changes the height of the widget. Optional, returns
+    /// the height by default.
+    fn highlighted_height(&self) -> usize {
+        self.height()
+    }
+
     /// Highlight the selected widget. Optional.
     #[must_use]
     fn highlight(self) -> Self
@@ -13,4 +98,105 @@ pub trait Listable: Widget {
     {
         self
     }
+
+    /// Highlight the selected widget with the theme of the list.
+    /// Optional, calls [`Listable::highlight`] by default.
+    #[must_use]
+    fn highlight_with(self, _theme: &HighlightTheme) -> Self
+    where
+        Self: Sized,
+    {
+        self.highlight()
+    }
+
+    /// Renders the selected widget. Used instead of highlighting and
+    /// rendering the widget, e.g. to render an inline editor in place of
+    /// the selected widget. The area is as high as
+    /// [`Listable::highlighted_height`], unless the widget is truncated.
+    /// Optional, renders the widget highlighted with the theme of the list
+    /// by default, see [`Listable::highlight_with`].
+    fn render_selected(self, area: Rect, buf: &mut Buffer, theme: &HighlightTheme)
+    where
+        Self: Sized,
+    {
+        self.highlight_with(theme).render(area, buf);
+    }
+
+    /// The symbol drawn left of the widget if it is selected, e.g. to show
+    /// whether a node of a tree is expanded. Optional, falls back to the
+    /// highlight symbol of the list by default.
+    fn highlight_symbol(&self) -> Option<&str> {
+        None
+    }
+
+    /// Mark a widget that is part of the multi-selection of the list, see
+    /// `ListState::toggle_selection`. Applied on render in addition to the
+    /// highlight of the selected widget. Must not change the height of the
+    /// widget. Optional.
+    #[must_use]
+    fn mark(self) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Pass the context in which the widget is rendered. Called on every
+    /// widget on the viewport before it is rendered. Optional.
+    #[must_use]
+    fn context(self, _context: &ItemContext) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Hide the given number of rows at the top of the widget. Called if
+    /// the widget is cut off at the top of the viewport, before it is
+    /// rendered into the remaining rows. Optional, by default the widget
+    /// is rendered as is, which cuts off its bottom rows instead.
+    #[must_use]
+    fn truncate_top(self, _hidden_rows: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Called with the given number of rows that are cut off at the bottom
+    /// of the widget, if it is cut off at the bottom of the viewport, before
+    /// it is rendered into the remaining rows, e.g. to end with an ellipsis.
+    /// If the widget is larger than the viewport, both truncation hooks may
+    /// be called. Optional, by default the widget is rendered as is, which
+    /// cuts off its bottom rows.
+    #[must_use]
+    fn truncate_bottom(self, _hidden_rows: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Whether a separator is drawn beneath the item. Has only an effect if
+    /// the `List` has a separator configured. Optional, false by default.
+    /// `List::separate_all` draws the separator beneath all items instead.
+    fn separator_after(&self) -> bool {
+        false
+    }
+
+    /// Whether the item can be selected, e.g. false for a section header.
+    /// See `ListState::select_nearest`. Optional, true by default.
+    fn selectable(&self) -> bool {
+        true
+    }
+}
+
+/// Can be implemented on widget list items that have a stable key, i.e.
+/// a value that identifies the item independent of its position in the list.
+pub trait Keyed: Listable {
+    /// The type of the key.
+    type Key: PartialEq;
+
+    /// Returns the key of the item.
+    fn key(&self) -> Self::Key;
 }
@@ -1,10 +1,112 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+};
+
 use ratatui::{
+    layout::Corner,
     prelude::{Buffer, Rect},
-    style::Style,
-    widgets::{Block, StatefulWidget, Widget},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Scrollbar, StatefulWidget, Widget},
+};
+
+use crate::{
+    state::{ItemLayout, Transition, Truncate},
+    HighlightTheme, ItemContext, JumpMode, Keyed, ListDirection, ListState, Listable,
+    ScrollStrategy,
 };
 
-use crate::{ListState, Listable};
+/// The items of a [`List`]. Items are either owned by the list, borrowed
+/// from the application or shared between lists.
+#[derive(Clone)]
+enum Items<'a, T> {
+    Owned(Vec<T>),
+    /// Borrowed items are cloned on render. This only affects the
+    /// items that are shown on the viewport.
+    Borrowed(&'a [T], fn(&T) -> T),
+    /// Shared items are cloned on render like borrowed items.
+    Shared(Arc<[T]>, fn(&T) -> T),
+}
+
+impl<'a, T: Listable> Items<'a, T> {
+    fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Owned(items) => items,
+            Self::Borrowed(items, _) => items,
+            Self::Shared(items, _) => items,
+        }
+    }
+
+    /// Returns the owned items. Borrowed and shared items are cloned
+    /// into an owned vector first.
+    fn to_mut(&mut self) -> &mut Vec<T> {
+        match self {
+            Self::Owned(items) => items,
+            Self::Borrowed(items, clone) => {
+                *self = Self::Owned(items.iter().map(*clone).collect());
+                self.to_mut()
+            }
+            Self::Shared(items, clone) => {
+                *self = Self::Owned(items.iter().map(*clone).collect());
+                self.to_mut()
+            }
+        }
+    }
+
+    /// Splits off the items before the given index.
+    fn split_front(&mut self, at: usize) -> Vec<T> {
+        match self {
+            Self::Owned(items) => items.drain(..at).collect(),
+            Self::Borrowed(items, clone) => {
+                let (head, tail) = items.split_at(at);
+                *items = tail;
+                head.iter().map(*clone).collect()
+            }
+            Self::Shared(items, clone) => {
+                let head = items[..at].iter().map(*clone).collect();
+                *self = Self::Owned(items[at..].iter().map(*clone).collect());
+                head
+            }
+        }
+    }
+
+    /// Splits off the items from the given index onwards.
+    fn split_off(&mut self, at: usize) -> Vec<T> {
+        match self {
+            Self::Owned(items) => items.split_off(at),
+            Self::Borrowed(items, clone) => {
+                let (head, tail) = items.split_at(at);
+                *items = head;
+                tail.iter().map(*clone).collect()
+            }
+            Self::Shared(items, clone) => {
+                let tail = items[at..].iter().map(*clone).collect();
+                *self = Self::Owned(items[..at].iter().map(*clone).collect());
+                tail
+            }
+        }
+    }
+
+    /// Moves the items of the given range out of the list.
+    fn into_range(self, range: Range<usize>) -> Vec<T> {
+        match self {
+            Self::Owned(mut items) => items.drain(range).collect(),
+            Self::Borrowed(items, clone) => items[range].iter().map(clone).collect(),
+            Self::Shared(items, clone) => items[range].iter().map(clone).collect(),
+        }
+    }
+}
+
+/// A function that computes the title of the block from the selected item.
+type BlockTitleFn<'a, T> = Rc<dyn Fn(Option<&T>) -> Line<'a> + 'a>;
+
+/// A function that is called with the old and the new index if the list
+/// clamps the selection.
+type ClampFn<'a> = Rc<dyn Fn(usize, usize) + 'a>;
 
 /// A [`List`] is a widget that can be used in Ratatui to
 /// render an arbitrary list of widgets. It is generic over
@@ -12,7 +114,7 @@ use crate::{ListState, Listable};
 #[derive(Clone)]
 pub struct List<'a, T: Listable> {
     /// The lists items.
-    pub items: Vec<T>,
+    items: Items<'a, T>,
 
     /// Style used as a base style for the widget.
     style: Style,
@@ -20,8 +122,119 @@ pub struct List<'a, T: Listable> {
     /// Block surrounding the widget list.
     block: Option<Block<'a>>,
 
-    /// Truncate widgets to fill full screen. Defaults to true.
-    truncate: bool,
+    /// Computes the title of the block from the selected item.
+    block_title_fn: Option<BlockTitleFn<'a, T>>,
+
+    /// Truncate widgets at the edges to fill full screen. Defaults to true.
+    truncate: Truncate,
+
+    /// Whether the last item can be scrolled to the top of the viewport.
+    /// Defaults to false.
+    overscroll_end: bool,
+
+    /// Whether items wrap their content. Defaults to false.
+    wrap: bool,
+
+    /// The minimum and the maximum height of an item.
+    item_height_range: (usize, usize),
+
+    /// The height of items that have a height of zero. Defaults to zero.
+    default_item_height: usize,
+
+    /// The corner of the viewport at which the first item is laid out.
+    /// Defaults to the top left corner.
+    start_corner: Corner,
+
+    /// Whether the items are laid out in reverse order. Defaults to false.
+    reversed: bool,
+
+    /// How the viewport follows a jump to an item. Defaults to instant.
+    jump_mode: JumpMode,
+
+    /// How the viewport follows the selection if the user navigates.
+    scroll_strategy: ScrollStrategy,
+
+    /// How the viewport follows the selection if it is selected programmatically.
+    programmatic_scroll_strategy: ScrollStrategy,
+
+    /// The number of rows that are kept visible above and below the selection.
+    scroll_padding: usize,
+
+    /// The symbol drawn left of the selected item.
+    highlight_symbol: Option<&'a str>,
+
+    /// The style of the highlight symbol.
+    highlight_symbol_style: Style,
+
+    /// The width of the gutter of the highlight symbol, if it is fixed.
+    highlight_symbol_width: Option<u16>,
+
+    /// Whether the highlight spans the full width of the list.
+    highlight_full_width: bool,
+
+    /// The theme passed to the selected item.
+    highlight_theme: HighlightTheme,
+
+    /// The style patched over the selected item after it is rendered.
+    highlight_style: Style,
+
+    /// The separator drawn beneath items whose `separator_after` is true.
+    separator: Option<Line<'a>>,
+
+    /// Whether the separator is drawn beneath all items, regardless of
+    /// `separator_after`.
+    separate_all: bool,
+
+    /// The number of rows reserved for a separator. Defaults to 1.
+    separator_height: usize,
+
+    /// The line drawn at the drop target of the state, if any.
+    drop_indicator: Option<Line<'a>>,
+
+    /// The style of the separator rows and of the rows below the items.
+    gap_style: Style,
+
+    /// The selection that is forced onto the state on render, if any.
+    selected: Option<Option<usize>>,
+
+    /// Called if the selection is clamped on render.
+    on_clamp: Option<ClampFn<'a>>,
+
+    /// The number of items at the end that are pinned to the bottom.
+    frozen_bottom: usize,
+
+    /// The heights of the items measured for an assumed width.
+    assumed_heights: Option<(u16, Vec<usize>)>,
+
+    /// Whether indicators are drawn if items are hidden. Defaults to false.
+    scroll_indicators: bool,
+
+    /// Whether the numbers of the layout are drawn. Defaults to false.
+    debug_overlay: bool,
+
+    /// The number of columns the items flow into. Defaults to one.
+    flow_columns: usize,
+
+    /// Whether the offset follows the selection. Defaults to true.
+    auto_scroll: bool,
+
+    /// Whether the items are shown in their compact form. Defaults to false.
+    compact: bool,
+
+    /// Whether the selection is shown and can be changed. Defaults to true.
+    highlightable: bool,
+
+    /// An optional scrollbar in the right column of the viewport.
+    scrollbar: Option<Scrollbar<'a>>,
+
+    /// The direction in which the items are laid out. Defaults to vertical.
+    direction: ListDirection,
+
+    /// The number of items at the start that are pinned to the top.
+    sticky_header: usize,
+
+    /// The number of blank rows between the items. Defaults to zero.
+    spacing: u16,
 }
 
 impl<'a, T: Listable> List<'a, T> {
@@ -29,11 +242,85 @@ impl<'a, T: Listable> List<'a, T> {
     /// implement the [`WidgetItem`] trait.
     #[must_use]
     pub fn new(items: Vec<T>) -> Self {
+        Self::with_items(Items::Owned(items))
+    }
+
+    /// Instantiate a widget list from an iterator with a known length.
+    /// The elements are collected in one pass into a pre-sized vector.
+    #[must_use]
+    pub fn from_exact_iter<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T, IntoIter: ExactSizeIterator>,
+    {
+        let items = items.into_iter();
+        let mut owned = Vec::with_capacity(items.len());
+        owned.extend(items);
+        Self::new(owned)
+    }
+
+    /// Instantiate a widget list that borrows its elements. Only the
+    /// elements that are shown on the viewport are cloned on render.
+    #[must_use]
+    pub fn from_slice(items: &'a [T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_items(Items::Borrowed(items, T::clone))
+    }
+
+    /// Instantiate a widget list that shares its elements with other lists.
+    /// Cloning the list does not clone the elements. Only the elements that
+    /// are shown on the viewport are cloned on render.
+    #[must_use]
+    pub fn from_arc(items: Arc<[T]>) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_items(Items::Shared(items, T::clone))
+    }
+
+    fn with_items(items: Items<'a, T>) -> Self {
         Self {
             items,
             style: Style::default(),
             block: None,
-            truncate: true,
+            block_title_fn: None,
+            truncate: Truncate::default(),
+            overscroll_end: false,
+            wrap: false,
+            item_height_range: (0, usize::MAX),
+            default_item_height: 0,
+            start_corner: Corner::TopLeft,
+            reversed: false,
+            jump_mode: JumpMode::default(),
+            scroll_strategy: ScrollStrategy::default(),
+            programmatic_scroll_strategy: ScrollStrategy::default(),
+            scroll_padding: 0,
+            highlight_symbol: None,
+            highlight_symbol_style: Style::default(),
+            highlight_symbol_width: None,
+            highlight_full_width: false,
+            highlight_theme: HighlightTheme::default(),
+            highlight_style: Style::default(),
+            separator: None,
+            separate_all: false,
+            separator_height: 1,
+            drop_indicator: None,
+            gap_style: Style::default(),
+            selected: None,
+            on_clamp: None,
+            frozen_bottom: 0,
+            assumed_heights: None,
+            scroll_indicators: false,
+            debug_overlay: false,
+            flow_columns: 1,
+            auto_scroll: true,
+            highlightable: true,
+            compact: false,
+            scrollbar: None,
+            direction: ListDirection::Vertical,
+            sticky_header: 0,
+            spacing: 0,
         }
     }
 
@@ -44,6 +331,22 @@ impl<'a, T: Listable> List<'a, T> {
         self
     }
 
+    /// Removes the block around the list, e.g. if a list that is reused
+    /// across screens is shown without borders.
+    pub fn clear_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Set a function that computes the title of the block from the
+    /// selected item on render. The function receives none if no item is
+    /// selected. The title is added to the titles of the block, so the
+    /// block should not have a title of its own. Has no effect without a block.
+    #[must_use]
+    pub fn block_title_fn(mut self, title_fn: impl Fn(Option<&T>) -> Line<'a> + 'a) -> Self {
+        self.block_title_fn = Some(Rc::new(title_fn));
+        self
+    }
+
     /// Set the base style of the List.
     #[must_use]
     pub fn style(mut self, style: Style) -> Self {
@@ -56,111 +359,4620 @@ impl<'a, T: Listable> List<'a, T> {
     /// It is true by default.
     #[must_use]
     pub fn truncate(mut self, truncate: bool) -> Self {
-        self.truncate = truncate;
+        self.truncate = Truncate::both(truncate);
         self
     }
 
-    /// Whether the widget list is empty
+    /// If truncate top is true, the first item on the screen may be
+    /// truncated. Otherwise the offset is aligned to the top of the
+    /// first item. It is true by default.
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.items.is_empty()
+    pub fn truncate_top(mut self, truncate_top: bool) -> Self {
+        self.truncate.top = truncate_top;
+        self
     }
 
-    /// Returns the length of the widget list
+    /// If truncate bottom is true, the last item on the screen may be
+    /// truncated to fill the screen. It is true by default.
     #[must_use]
-    pub fn len(&self) -> usize {
-        self.items.len()
+    pub fn truncate_bottom(mut self, truncate_bottom: bool) -> Self {
+        self.truncate.bottom = truncate_bottom;
+        self
     }
-}
 
-impl<'a, T: Listable> From<Vec<T>> for List<'a, T> {
-    /// Instantiates a [`List`] from a vector of elements implementing
-    /// the [`Listable`] trait.
-    fn from(items: Vec<T>) -> Self {
-        Self::new(items)
+    /// If overscroll end is true, the viewport can be scrolled with
+    /// [`ListState::scroll_down`] until the last item is at the top of
+    /// the viewport. The remaining area is filled with the base style.
+    /// It is false by default.
+    #[must_use]
+    pub fn overscroll_end(mut self, overscroll_end: bool) -> Self {
+        self.overscroll_end = overscroll_end;
+        self
     }
-}
 
-impl<'a, T: Listable> StatefulWidget for List<'a, T> {
-    type State = ListState;
-    // Renders a mutable reference to a widget list
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let mut items = self.items;
-        let mut block = self.block;
-        state.set_num_elements(items.len());
+    /// If wrap is true, the heights of the items are measured with
+    /// [`Listable::height_for_width`] and the items are told to wrap
+    /// their content via [`ItemContext::wrap`].
+    /// It is false by default.
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
 
-        // Set the base style
-        buf.set_style(area, self.style);
-        let area = match block.take() {
-            Some(b) => {
-                let inner_area = b.inner(area);
-                b.render(area, buf);
-                inner_area
-            }
-            None => area,
-        };
+    /// Clamp the heights of all items to the given range before they are
+    /// laid out, e.g. so that an item of height zero still takes one row.
+    /// The rows of an item beyond the maximum height are cut off. If the
+    /// minimum is larger than the maximum, the maximum takes precedence.
+    #[must_use]
+    pub fn clamp_item_height(mut self, min: u16, max: u16) -> Self {
+        self.item_height_range = (min.into(), max.into());
+        self
+    }
 
-        // List is empty
-        if items.is_empty() {
-            return;
-        }
+    /// Set the height of the items that return a height of zero, e.g. so
+    /// that items of a mock-up do not need to implement a meaningful
+    /// [`Listable::height`]. Items with a non-zero height keep their height.
+    /// It is zero by default.
+    #[must_use]
+    pub fn default_item_height(mut self, height: u16) -> Self {
+        self.default_item_height = height.into();
+        self
+    }
 
-        // Use the full width
-        let width = area.width;
+    /// Measure the heights of the items for the given width once, instead
+    /// of on every render. Has only an effect if wrap is true. The heights
+    /// are reused as long as the items are rendered with this width, i.e.
+    /// the width of the list without the gutter of the highlight symbol,
+    /// and measured again otherwise. Call this after all items are added.
+    #[must_use]
+    pub fn assume_width(mut self, width: u16) -> Self {
+        let heights = self
+            .items()
+            .iter()
+            .map(|item| item.height_for_width(width))
+            .collect();
+        self.assumed_heights = Some((width, heights));
+        self
+    }
 
-        // Maximum height
-        let max_height = area.height as usize;
+    /// Set the corner at which the list starts. If it is a bottom corner,
+    /// the items are stacked upwards from the bottom of the viewport, and
+    /// a short list leaves the rows at the top empty. The offset of the
+    /// [`ListState`] counts from this corner. It is the top left corner
+    /// by default.
+    #[must_use]
+    pub fn start_corner(mut self, start_corner: Corner) -> Self {
+        self.start_corner = start_corner;
+        self
+    }
 
-        // The starting positions of the current item
-        let x = area.left();
-        let y0 = area.top();
-        let mut y = y0;
+    /// If reversed is true, the last item is laid out first at the start
+    /// corner of the list, and the first item last. False by default.
+    #[must_use]
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
 
-        // Split out the highlighted item
-        let mut highlighted: Option<T> = None;
-        if let Some(index) = state.selected() {
-            if index < items.len() {
-                highlighted = Some(items.remove(index).highlight());
-            }
-        }
+    /// Set how the viewport follows a jump to an item, e.g. in
+    /// [`List::select_by_key`]. It is [`JumpMode::Instant`] by default.
+    #[must_use]
+    pub fn jump_mode(mut self, jump_mode: JumpMode) -> Self {
+        self.jump_mode = jump_mode;
+        self
+    }
 
-        // Modify the widgets based on their selection state. Split out their heights
-        // for efficiency as we have to iterate over the heights back and forth to
-        // determine which widget is shown on the viewport.
-        let mut raw_heights: Vec<_> = items.iter().map(Listable::height).collect();
+    /// Set how the viewport follows the selection if the user navigates,
+    /// e.g. with [`ListState::next`] or [`ListState::click`]. It is
+    /// [`ScrollStrategy::Edge`] by default.
+    #[must_use]
+    pub fn scroll_strategy(mut self, strategy: ScrollStrategy) -> Self {
+        self.scroll_strategy = strategy;
+        self
+    }
 
-        // Insert the height of the highlighted item back in
-        if let (Some(index), Some(h)) = (state.selected, &mut highlighted) {
-            raw_heights.insert(index, h.height());
-        }
+    /// Set how the viewport follows the selection if an item is selected
+    /// programmatically, e.g. with [`ListState::select`] or
+    /// [`List::select_by_key`]. It is [`ScrollStrategy::Edge`] by default.
+    #[must_use]
+    pub fn programmatic_scroll_strategy(mut self, strategy: ScrollStrategy) -> Self {
+        self.programmatic_scroll_strategy = strategy;
+        self
+    }
 
-        // Determine which widgets to show on the viewport and how much space they
-        // get assigned to. The number of elements in `view_heights` is less than
-        // the number of elements in `raw_heights` if not all widgets are shown
-        // on the viewport.
-        let view_heights = state.update_view_port(&raw_heights, max_height, self.truncate);
+    /// Set the number of rows of other items that are kept visible above
+    /// and below the selected item, like the scrolloff of vim. Near the start
+    /// and the end of the list, the padding shrinks to the rows of the items
+    /// that are left. If the viewport is too small for the padding on both
+    /// sides, the padding below takes precedence. Zero by default.
+    #[must_use]
+    pub fn scroll_padding(mut self, rows: usize) -> Self {
+        self.scroll_padding = rows;
+        self
+    }
 
-        // Drain out elements that are shown on the view port from the vector of
-        // all elements.
-        let first = state.offset;
-        let mut last = view_heights.len() + first;
-        if highlighted.is_some() {
-            last = last.saturating_sub(1);
-        }
-        let mut view_items = items.drain(first..last);
+    /// Set the selection that is applied to the state on every render, e.g.
+    /// if the selection is owned by a controller outside of the widget.
+    /// If it differs from the selection of the state, it overrides any
+    /// navigation on the state since the last render and brings the selected
+    /// item into view. Otherwise the state is left as is, so that explicit
+    /// scrolling, e.g. with [`ListState::scroll_down`], is kept. Applying the
+    /// selection emits no event, see [`ListState::set_event_sink`].
+    #[must_use]
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = Some(selected);
+        self
+    }
 
-        // Iterate over the modified items
-        let offset = state.offset;
-        for (i, height) in view_heights.into_iter().enumerate() {
-            let area = Rect::new(x, y, width, height as u16);
-            if state.selected().is_some_and(|s| s == i + offset) {
-                if let Some(item) = highlighted.take() {
-                    item.render(area, buf);
+    /// Set a function that is called on render if the selection is out of
+    /// bounds and clamped to the last item, with the old and the new index.
+    /// The clamped selection is written back to the [`ListState`].
+    #[must_use]
+    pub fn on_clamp(mut self, on_clamp: impl Fn(usize, usize) + 'a) -> Self {
+        self.on_clamp = Some(Rc::new(on_clamp));
+        self
+    }
+
+    /// Pin the given number of items at the start of the list to the top
+    /// of the viewport, e.g. for a header that never scrolls. The other
+    /// items scroll in the rows below the pinned items. The pinned items
+    /// are skipped by the navigation of the state, but can be selected
+    /// explicitly, e.g. with [`ListState::select`]. If the pinned items are
+    /// higher than the viewport, they are cut off at the bottom.
+    #[must_use]
+    pub fn sticky_header(mut self, count: usize) -> Self {
+        self.sticky_header = count;
+        self
+    }
+
+    /// Pin the given number of items at the end of the list to the bottom
+    /// of the viewport, e.g. for an action that should always be shown.
+    /// The other items scroll in the rows above the pinned items. The
+    /// pinned items can be selected like any other item.
+    #[must_use]
+    pub fn frozen_bottom(mut self, count: usize) -> Self {
+        self.frozen_bottom = count;
+        self
+    }
+
+    /// If true, indicators are drawn in the right column of the viewport
+    /// if items are hidden above or below it. A click on an indicator can
+    /// be detected with [`ListState::element_at`]. False by default.
+    #[must_use]
+    pub fn scroll_indicators(mut self, scroll_indicators: bool) -> Self {
+        self.scroll_indicators = scroll_indicators;
+        self
+    }
+
+    /// If true, the offset, the viewport height, the selected index and the
+    /// index and height of each visible item are drawn in the top right
+    /// corner of the viewport, e.g. to debug or to report layout issues.
+    /// Never enable this in a release. False by default.
+    #[must_use]
+    pub fn debug_overlay(mut self, debug_overlay: bool) -> Self {
+        self.debug_overlay = debug_overlay;
+        self
+    }
+
+    /// If compact is true, the items are shown in their compact form, e.g.
+    /// to fit more items on the screen, see [`Listable::compact`]. The
+    /// items are laid out with [`Listable::compact_height`]. The selected
+    /// item stays in view if the density is toggled. False by default.
+    #[must_use]
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// If auto scroll is false, the viewport does not follow the selection,
+    /// e.g. in a reader whose offset is managed by the app. The offset then
+    /// only changes by explicit scrolling, e.g. with [`ListState::scroll_down`],
+    /// and is clamped to the items. True by default.
+    #[must_use]
+    pub fn auto_scroll(mut self, auto_scroll: bool) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+
+    /// If highlightable is false, the list is a pure scroller for display:
+    /// no item is highlighted or marked on render, and the navigation of the
+    /// state, e.g. [`ListState::next`], does nothing. The selection of the
+    /// state is kept, but it is ignored on render until the list is
+    /// highlightable again. The viewport can still be scrolled, e.g. with
+    /// [`ListState::scroll_down`]. True by default.
+    #[must_use]
+    pub fn highlightable(mut self, highlightable: bool) -> Self {
+        self.highlightable = highlightable;
+        self
+    }
+
+    /// Set a vertical scrollbar that is drawn in the right column inside
+    /// the block, next to the items. Its thumb shows the rows of the items
+    /// on the viewport among the rows of all items. A click on the track
+    /// above or below the thumb can be detected with [`ListState::element_at`]
+    /// and scrolls a page with [`ListState::click`].
+    #[must_use]
+    pub fn scrollbar(mut self, scrollbar: Option<Scrollbar<'a>>) -> Self {
+        self.scrollbar = scrollbar;
+        self
+    }
+
+    /// Set the direction in which the items are laid out. If horizontal,
+    /// the items are laid out from left to right, e.g. as a carousel of
+    /// cards, and are measured by [`Listable::width`]. Items without a width
+    /// take the full width of the viewport. The navigation and the offset
+    /// of the state are the same as for a vertical list, but count columns
+    /// instead of rows, e.g. [`ListState::scroll_to_row`] scrolls by
+    /// columns. The item at the right edge is truncated. The
+    /// start corner, the order, the separator, the spacing, the highlight
+    /// symbol, the frozen items and the flow columns of the list do not
+    /// apply. Vertical by default.
+    #[must_use]
+    pub fn direction(mut self, direction: ListDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the number of columns the items flow into, like the columns of
+    /// a newspaper. The items fill the first column from top to bottom,
+    /// then the next column, and so on. The navigation keeps the order of
+    /// the items, and the viewport scrolls by all columns at once. Items
+    /// are not truncated to fill a column, and the start corner, the order,
+    /// the separator, the spacing, the highlight symbol and the frozen items
+    /// of the list do not apply to the columns. There are at most as many
+    /// columns as the list is wide. One column by default.
+    #[must_use]
+    pub fn flow_columns(mut self, columns: usize) -> Self {
+        self.flow_columns = columns.max(1);
+        self
+    }
+
+    /// Set the symbol that is drawn left of the selected item, unless the
+    /// item has its own symbol, see [`Listable::highlight_symbol`]. A gutter
+    /// with the width of the widest symbol is reserved left of all items.
+    #[must_use]
+    pub fn highlight_symbol(mut self, highlight_symbol: &'a str) -> Self {
+        self.highlight_symbol = Some(highlight_symbol);
+        self
+    }
+
+    /// Set the style of the highlight symbol. It is applied on top of the
+    /// base style, independent of how the selected item is highlighted.
+    #[must_use]
+    pub fn highlight_symbol_style(mut self, style: Style) -> Self {
+        self.highlight_symbol_style = style;
+        self
+    }
+
+    /// Reserve a gutter of a fixed width for the highlight symbols instead
+    /// of the width of the widest symbol. Symbols that are wider than the
+    /// gutter are cut off by their display width, e.g. a wide emoji that
+    /// does not fit is not drawn. The gutter is as wide as the widest
+    /// symbol by default.
+    #[must_use]
+    pub fn highlight_symbol_width(mut self, width: u16) -> Self {
+        self.highlight_symbol_width = Some(width);
+        self
+    }
+
+    /// If highlight full width is true, the background of the highlight
+    /// theme is applied to all cells in the rows of the selected item that
+    /// the item left in the base style, including the highlight symbol gutter.
+    /// It is false by default.
+    #[must_use]
+    pub fn highlight_full_width(mut self, highlight_full_width: bool) -> Self {
+        self.highlight_full_width = highlight_full_width;
+        self
+    }
+
+    /// Set the style that is patched over the rows of the selected item
+    /// after it is rendered. It takes precedence over all other styles.
+    #[must_use]
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Set the theme that is passed to the selected item in
+    /// [`Listable::highlight_with`].
+    #[must_use]
+    pub fn highlight_theme(mut self, theme: HighlightTheme) -> Self {
+        self.highlight_theme = theme;
+        self
+    }
+
+    /// Set the separator that is drawn beneath items. Whether an item
+    /// is followed by a separator is decided by [`Listable::separator_after`],
+    /// or by [`List::separate_all`]. No separator is drawn beneath the last
+    /// item of the list.
+    #[must_use]
+    pub fn separator(mut self, separator: impl Into<Line<'a>>) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    /// If true, the separator is drawn between every two consecutive items,
+    /// regardless of [`Listable::separator_after`]. Has only an effect if
+    /// a separator is configured. False by default.
+    #[must_use]
+    pub fn separate_all(mut self, separate_all: bool) -> Self {
+        self.separate_all = separate_all;
+        self
+    }
+
+    /// Set the number of rows reserved for a separator. The separator
+    /// line is drawn in the first of these rows. Defaults to 1.
+    #[must_use]
+    pub fn separator_height(mut self, height: usize) -> Self {
+        self.separator_height = height;
+        self
+    }
+
+    /// Set the number of blank rows between the items, in addition to the
+    /// rows of the separators. The rows take the base style and the gap
+    /// style of the list. No spacing is added beneath the last item. Zero
+    /// by default.
+    #[must_use]
+    pub fn spacing(mut self, rows: u16) -> Self {
+        self.spacing = rows;
+        self
+    }
+
+    /// Set the style of the rows between the items, i.e. the rows of the
+    /// separators and of the spacing, and of the empty rows below the last
+    /// item. It is applied on top of the base style.
+    #[must_use]
+    pub fn gap_style(mut self, style: Style) -> Self {
+        self.gap_style = style;
+        self
+    }
+
+    /// Set the line that is drawn at the drop target of the state, e.g. to
+    /// show where a dragged item would be inserted, see
+    /// [`ListState::set_drop_target`]. The line is drawn in the gap between
+    /// the target item and the previous item, e.g. over the separator. A row
+    /// is reserved for it if there is no gap, so the items stay intact.
+    #[must_use]
+    pub fn drop_indicator(mut self, indicator: impl Into<Line<'a>>) -> Self {
+        self.drop_indicator = Some(indicator.into());
+        self
+    }
+
+    /// Returns the items that were shown on the viewport in the last
+    /// render together with their indices, including the items that are
+    /// partially visible at the edges. See [`ListState::visible_range`].
+    pub fn visible_items<'b>(
+        &'b self,
+        state: &ListState,
+    ) -> impl Iterator<Item = (usize, &'b T)> + 'b {
+        let items = self.items();
+        let range = state.visible_range();
+        let range = range.start.min(items.len())..range.end.min(items.len());
+        range.clone().zip(&items[range])
+    }
+
+    /// Returns the number of items from the given index onwards that fit
+    /// into the given number of rows without being truncated, e.g. to
+    /// compute the size of a page. The items are measured like on render,
+    /// except that wrapped items are measured only for an assumed width,
+    /// see [`List::assume_width`]. Has no side effects.
+    #[must_use]
+    pub fn items_fitting(&self, start: usize, height: u16) -> usize {
+        let assumed = self.assumed_heights.as_ref().filter(|_| self.wrap);
+        let mut rows = 0;
+        self.items()
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take_while(|&(i, item)| {
+                let item_height = self.item_height(item, || match assumed {
+                    Some((_, heights)) if i < heights.len() => heights[i],
+                    _ => item.height(),
+                });
+                let separator = if self.separated(item) {
+                    self.separator_height
+                } else {
+                    0
+                };
+                rows += item_height;
+                let fits = rows <= height as usize;
+                if item_height > 0 {
+                    rows += separator + self.spacing as usize;
                 }
-            } else if let Some(item) = view_items.next() {
-                item.render(area, buf);
-            }
-            y += height as u16;
-        }
+                fits
+            })
+            .count()
+    }
+
+    /// Returns the items of the widget list
+    #[must_use]
+    pub fn items(&self) -> &[T] {
+        self.items.as_slice()
+    }
+
+    /// Returns the items of the widget list mutably, e.g. to edit, remove or
+    /// reorder items in place. Borrowed or shared items are cloned into an
+    /// owned vector first. Heights that were measured for an assumed width
+    /// are not updated, see [`List::assume_width`].
+    pub fn items_mut(&mut self) -> &mut Vec<T> {
+        self.items.to_mut()
+    }
+
+    /// Returns the selected item, or none if no item is selected.
+    #[must_use]
+    pub fn selected_item<'s>(&'s self, state: &ListState) -> Option<&'s T> {
+        state.selected().and_then(|index| self.items().get(index))
+    }
+
+    /// Returns the selected item mutably, or none if no item is selected.
+    /// Borrowed or shared items are cloned into an owned vector first.
+    pub fn selected_item_mut<'s>(&'s mut self, state: &ListState) -> Option<&'s mut T> {
+        let index = state.selected()?;
+        self.items.to_mut().get_mut(index)
+    }
+
+    /// Edits the selected item in place, e.g. to toggle a checkbox. The
+    /// height of the item is measured again afterwards if the heights were
+    /// measured for an assumed width, see [`List::assume_width`]. Returns
+    /// false and does not call the function if no item is selected.
+    pub fn with_selected_mut(&mut self, state: &ListState, f: impl FnOnce(&mut T)) -> bool {
+        let Some(index) = state.selected().filter(|&index| index < self.len()) else {
+            return false;
+        };
+        let item = &mut self.items.to_mut()[index];
+        f(item);
+        if let Some((width, heights)) = &mut self.assumed_heights {
+            if let Some(height) = heights.get_mut(index) {
+                *height = item.height_for_width(*width);
+            }
+        }
+        true
+    }
+
+    /// Appends an item to the widget list. Borrowed or shared items
+    /// are cloned into an owned vector first.
+    pub fn push(&mut self, item: T) {
+        self.items.to_mut().push(item);
+    }
+
+    /// Appends all items to the widget list. Prefer this over repeated
+    /// calls to [`List::push`] when adding many items, as the storage is
+    /// grown once. Item heights are measured on render, so appending does
+    /// not invalidate any cached layout.
+    pub fn extend_items(&mut self, items: impl IntoIterator<Item = T>) {
+        self.items.to_mut().extend(items);
+    }
+
+    /// Moves the item at `from` to the index `to`, e.g. to reorder the items
+    /// by dragging. The items in between shift by one. The selection and the
+    /// marked items of the state follow the items, so a moved selected item
+    /// stays selected; select the index `from` again afterwards to keep the
+    /// selection in place instead. Does nothing if an index is out of bounds.
+    pub fn move_item(&mut self, state: &mut ListState, from: usize, to: usize) {
+        let len = self.len();
+        if from >= len || to >= len || from == to {
+            return;
+        }
+        let items = self.items.to_mut();
+        let item = items.remove(from);
+        items.insert(to, item);
+        if let Some((_, heights)) = &mut self.assumed_heights {
+            if heights.len() == len {
+                let height = heights.remove(from);
+                heights.insert(to, height);
+            }
+        }
+        state.reindex(|i| match i {
+            i if i == from => to,
+            i if from < to && (from..=to).contains(&i) => i - 1,
+            i if to < from && (to..=from).contains(&i) => i + 1,
+            i => i,
+        });
+    }
+
+    /// Swaps the items at the indices `a` and `b`. The selection and the
+    /// marked items of the state follow the items, see [`List::move_item`].
+    /// Does nothing if an index is out of bounds.
+    pub fn swap(&mut self, state: &mut ListState, a: usize, b: usize) {
+        let len = self.len();
+        if a >= len || b >= len || a == b {
+            return;
+        }
+        self.items.to_mut().swap(a, b);
+        if let Some((_, heights)) = &mut self.assumed_heights {
+            if heights.len() == len {
+                heights.swap(a, b);
+            }
+        }
+        state.reindex(|i| match i {
+            i if i == a => b,
+            i if i == b => a,
+            i => i,
+        });
+    }
+
+    /// Whether the widget list is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items().is_empty()
+    }
+
+    /// Returns the length of the widget list
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items().len()
+    }
+
+    /// Selects the first item that matches the query, starting at the
+    /// selected item, and scrolls it into view according to the jump mode
+    /// of the list. The matcher decides whether an item matches, e.g. by a
+    /// case-insensitive prefix. The search continues at the first item
+    /// after the last item if the wrap policy of the state allows it, see
+    /// [`crate::WrapPolicy::search`]. Returns false and leaves the selection
+    /// unchanged if no item matches or the list is not highlightable.
+    pub fn search(
+        &self,
+        state: &mut ListState,
+        query: &str,
+        matcher: impl Fn(&T, &str) -> bool,
+    ) -> bool {
+        let len = self.len();
+        let start = state.selected().unwrap_or(0).min(len);
+        let count = if state.wrap_policy.search {
+            len
+        } else {
+            len - start
+        };
+        let found = (start..start + count)
+            .map(|i| i % len)
+            .find(|&i| matcher(&self.items()[i], query));
+        let Some(index) = found else {
+            return false;
+        };
+        self.jump(state, index)
+    }
+
+    /// Selects and activates the first item of the list that matches the
+    /// query, e.g. in a quick-pick menu where a key press chooses an item.
+    /// Unlike [`List::search`], the search does not start at the selection.
+    /// The activated item is returned by [`ListState::take_activated`].
+    /// Returns the index of the item, or none if no item matches or the
+    /// list is not highlightable.
+    pub fn select_and_activate_matching(
+        &self,
+        state: &mut ListState,
+        query: &str,
+        matcher: impl Fn(&T, &str) -> bool,
+    ) -> Option<usize> {
+        let index = self.items().iter().position(|item| matcher(item, query))?;
+        if !self.jump(state, index) {
+            return None;
+        }
+        state.activate();
+        Some(index)
+    }
+
+    /// Selects the item with the best score for the query and scrolls it
+    /// into view according to the jump mode of the list, e.g. for fuzzy
+    /// matching. Items that score none do not match. If several items have
+    /// the best score, the first of them is selected. Returns false and
+    /// leaves the selection unchanged if no item matches or the list is not
+    /// highlightable.
+    pub fn search_best(
+        &self,
+        state: &mut ListState,
+        query: &str,
+        score_fn: impl Fn(&T, &str) -> Option<i64>,
+    ) -> bool {
+        let best = self
+            .items()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| score_fn(item, query).map(|score| (i, score)))
+            .fold(None, |best: Option<(usize, i64)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((i, score)),
+            });
+        let Some((index, _)) = best else {
+            return false;
+        };
+        self.jump(state, index)
+    }
+
+    /// Selects the item with the given index with the jump mode of the
+    /// list. The index is clamped to the length of the list. Returns false
+    /// if the navigation is disabled, as the list is not highlightable.
+    fn jump(&self, state: &mut ListState, index: usize) -> bool {
+        if state.navigation_disabled {
+            return false;
+        }
+        state.set_num_elements(self.len());
+        state.jump_to(index, self.jump_mode);
+        true
+    }
+
+    /// Selects the first item of the next group, e.g. to move across the
+    /// sections of a grouped list while [`ListState::next`] moves within
+    /// them. A group is a run of consecutive items for which `group_of`
+    /// returns the same value. Unselectable items are skipped, e.g. the
+    /// header of a group. The search wraps around the end of the list
+    /// unless single steps do not wrap, see [`ListState::wrap_policy`].
+    /// Returns false and leaves the selection unchanged if there is no
+    /// other group.
+    pub fn next_group_start<G: PartialEq>(
+        &self,
+        state: &mut ListState,
+        group_of: impl Fn(&T) -> G,
+    ) -> bool {
+        let starts = self.group_starts(state, group_of);
+        let current = state
+            .selected
+            .and_then(|selected| starts.iter().rposition(|&start| start <= selected));
+        let target = match current {
+            None => starts.first(),
+            Some(i) if i + 1 < starts.len() => starts.get(i + 1),
+            Some(i) if i > 0 && state.wrap_policy.step => starts.first(),
+            Some(_) => None,
+        };
+        self.select_group_start(state, target.copied())
+    }
+
+    /// Selects the first item of the previous group, see
+    /// [`List::next_group_start`]. If the selection is within a group,
+    /// the first item of the group before it is selected.
+    pub fn previous_group_start<G: PartialEq>(
+        &self,
+        state: &mut ListState,
+        group_of: impl Fn(&T) -> G,
+    ) -> bool {
+        let starts = self.group_starts(state, group_of);
+        let current = state
+            .selected
+            .and_then(|selected| starts.iter().rposition(|&start| start <= selected));
+        let last = starts.len().checked_sub(1);
+        let target = match current {
+            None => starts.last(),
+            Some(i) if i > 0 => starts.get(i - 1),
+            Some(i) if Some(i) != last && state.wrap_policy.step => starts.last(),
+            Some(_) => None,
+        };
+        self.select_group_start(state, target.copied())
+    }
+
+    /// Returns the index of the first selectable item of each group.
+    /// Groups without selectable items are left out.
+    fn group_starts<G: PartialEq>(
+        &self,
+        state: &ListState,
+        group_of: impl Fn(&T) -> G,
+    ) -> Vec<usize> {
+        let groups: Vec<G> = self.items().iter().map(group_of).collect();
+        let mut starts = Vec::new();
+        let mut group_start = 0;
+        for i in 0..groups.len() {
+            if i > 0 && groups[i] != groups[i - 1] {
+                group_start = i;
+            }
+            let found = starts.last().is_some_and(|&start| start >= group_start);
+            if !found && !state.unselectable.contains(&i) {
+                starts.push(i);
+            }
+        }
+        starts
+    }
+
+    /// Selects the given group start, see [`List::next_group_start`].
+    fn select_group_start(&self, state: &mut ListState, target: Option<usize>) -> bool {
+        let Some(index) = target.filter(|&index| state.selected != Some(index)) else {
+            return false;
+        };
+        state.set_selected(Some(index));
+        state.navigated = true;
+        true
+    }
+}
+
+impl<'a, T: Keyed> List<'a, T> {
+    /// Selects the item with the given key and scrolls it into view
+    /// according to the jump mode of the list. Returns false and leaves
+    /// the selection unchanged if no item has the key or the list is not
+    /// highlightable.
+    pub fn select_by_key(&self, state: &mut ListState, key: &T::Key) -> bool {
+        let Some(index) = self.items().iter().position(|item| item.key() == *key) else {
+            return false;
+        };
+        self.jump(state, index)
+    }
+
+    /// Remembers the key of the selected item in the state, so that the
+    /// selection can be restored with [`List::restore_selection`] after the
+    /// items were replaced, e.g. when the list was cleared and refilled.
+    /// Does nothing if no item is selected, so that a remembered key
+    /// survives renders of an empty list.
+    pub fn remember_selection(&self, state: &mut ListState)
+    where
+        T::Key: Hash,
+    {
+        let selected = state.selected().and_then(|index| self.items().get(index));
+        if let Some(item) = selected {
+            state.remembered_key = Some(hash_key(&item.key()));
+        }
+    }
+
+    /// Selects the item whose key was remembered with
+    /// [`List::remember_selection`] and scrolls it into view. Returns false
+    /// and keeps the remembered key if no item has the key yet.
+    pub fn restore_selection(&self, state: &mut ListState) -> bool
+    where
+        T::Key: Hash,
+    {
+        let Some(key) = state.remembered_key else {
+            return false;
+        };
+        let Some(index) = self
+            .items()
+            .iter()
+            .position(|item| hash_key(&item.key()) == key)
+        else {
+            return false;
+        };
+        state.remembered_key = None;
+        state.jump_to(index, self.jump_mode);
+        true
+    }
+
+    /// Replaces the items with new items, e.g. for a list of search results
+    /// that updates while the user types. The items are matched by their keys.
+    /// Inserted items fade in over the next renders. Removed items fade out
+    /// and then collapse; they stay in the list until the next update, but
+    /// they cannot be selected. The selected item stays selected by its key.
+    /// If it was removed, the nearest remaining item is selected instead.
+    /// Use [`ListState::is_animating`] to render again until the animations
+    /// are finished.
+    pub fn update_keyed(&mut self, new_items: Vec<T>, state: &mut ListState)
+    where
+        T::Key: Hash,
+    {
+        let old_items = std::mem::take(self.items.to_mut());
+        let old_transitions = std::mem::take(&mut state.transitions);
+        let old_keys: HashMap<u64, usize> = old_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (hash_key(&item.key()), i))
+            .collect();
+        let new_keys: HashMap<u64, usize> = new_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (hash_key(&item.key()), i))
+            .collect();
+        let selected_key = state
+            .selected()
+            .and_then(|index| old_items.get(index))
+            .map(|item| hash_key(&item.key()));
+
+        // Merge the new items with the removed items. A removed item stays
+        // behind the new items that precede it in the previous items.
+        let inserted = |item: T| {
+            let transition = match old_keys.get(&hash_key(&item.key())) {
+                // An item that is still fading in continues to do so
+                Some(i) => old_transitions.get(i).copied().filter(|t| !t.leaving),
+                None => Some(Transition {
+                    leaving: false,
+                    frames: TRANSITION_FRAMES,
+                }),
+            };
+            (item, transition)
+        };
+        let mut merged = Vec::with_capacity(old_items.len().max(new_items.len()));
+        let mut new_items = new_items.into_iter().enumerate().peekable();
+        for (i, item) in old_items.into_iter().enumerate() {
+            if let Some(&position) = new_keys.get(&hash_key(&item.key())) {
+                while let Some((_, item)) = new_items.next_if(|(j, _)| *j <= position) {
+                    merged.push(inserted(item));
+                }
+                continue;
+            }
+            let transition = match old_transitions.get(&i) {
+                // Removed items that collapsed already are dropped
+                Some(t) if t.leaving && t.frames == 0 => continue,
+                Some(t) if t.leaving => *t,
+                _ => Transition {
+                    leaving: true,
+                    frames: TRANSITION_FRAMES,
+                },
+            };
+            merged.push((item, Some(transition)));
+        }
+        merged.extend(new_items.map(|(_, item)| inserted(item)));
+
+        let selected = merged
+            .iter()
+            .position(|(item, _)| selected_key == Some(hash_key(&item.key())));
+        let transitions: HashMap<usize, Transition> = merged
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, transition))| transition.map(|t| (i, t)))
+            .collect();
+        let items: Vec<T> = merged.into_iter().map(|(item, _)| item).collect();
+
+        // Select the nearest remaining item if the selected item was removed
+        let remains = |i: &usize| !transitions.get(i).is_some_and(|t| t.leaving);
+        let selected = selected.and_then(|index| {
+            (0..items.len()).find_map(|distance| {
+                [index.checked_add(distance), index.checked_sub(distance)]
+                    .into_iter()
+                    .flatten()
+                    .find(|i| *i < items.len() && remains(i))
+            })
+        });
+        state.set_num_elements(items.len());
+        if state.selected().is_some() {
+            state.set_selected(selected);
+        }
+        state.transitions = transitions;
+        *self.items.to_mut() = items;
+    }
+}
+
+/// The number of renders during which an inserted item fades in and a
+/// removed item fades out, see [`List::update_keyed`].
+const TRANSITION_FRAMES: u16 = 4;
+
+/// Hashes a key so that it can be stored in the untyped [`ListState`].
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<'a, T: Listable> From<Vec<T>> for List<'a, T> {
+    /// Instantiates a [`List`] from a vector of elements implementing
+    /// the [`Listable`] trait.
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<'a, T: Listable + Clone> From<&'a [T]> for List<'a, T> {
+    /// Instantiates a [`List`] that borrows a slice of elements implementing
+    /// the [`Listable`] trait.
+    fn from(items: &'a [T]) -> Self {
+        Self::from_slice(items)
+    }
+}
+
+impl<'a, T: Listable + Clone> From<Arc<[T]>> for List<'a, T> {
+    /// Instantiates a [`List`] that shares elements implementing
+    /// the [`Listable`] trait.
+    fn from(items: Arc<[T]>) -> Self {
+        Self::from_arc(items)
+    }
+}
+
+impl<'a, T: Listable> StatefulWidget for List<'a, T> {
+    type State = ListState;
+    // Renders a mutable reference to a widget list
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let len = self.items.as_slice().len();
+        state.set_num_elements(len);
+        if let Some(selected) = self.selected.filter(|&index| index != state.selected) {
+            state.selected = selected;
+            state.navigated = false;
+            state.detached = false;
+            state.scrolling = false;
+        }
+        // A list that is not highlightable is rendered without a selection, but
+        // the selection of the state is kept for when it is highlightable again
+        state.navigation_disabled = !self.highlightable;
+        state.auto_scroll_disabled = !self.auto_scroll;
+        let kept_selection = if self.highlightable {
+            None
+        } else {
+            state.selected.take()
+        };
+
+        // Clamp the selection to the last item
+        if let Some(index) = state.selected.filter(|&index| len > 0 && index >= len) {
+            state.selected = Some(len - 1);
+            if let Some(on_clamp) = &self.on_clamp {
+                on_clamp(index, len - 1);
+            }
+        }
+
+        let mut block = self.block.take();
+        if let Some(title_fn) = &self.block_title_fn {
+            let selected = state
+                .selected()
+                .and_then(|index| self.items.as_slice().get(index));
+            block = block.map(|b| b.title(title_fn(selected)));
+        }
+        // Set the base style. The area inside the block is styled on render
+        // of the items, as opaque items are not styled beneath.
+        let area = match block {
+            Some(b) => {
+                let inner_area = b.inner(area);
+                set_style_outside(area, inner_area, buf, self.style);
+                b.render(area, buf);
+                inner_area
+            }
+            None => area,
+        };
+
+        // The scrollbar takes the right column of the area
+        let scrollbar = self.scrollbar.take().filter(|_| area.width > 1);
+        let (area, scrollbar) = match scrollbar {
+            Some(scrollbar) => {
+                let bar_area = Rect {
+                    x: area.right() - 1,
+                    width: 1,
+                    ..area
+                };
+                let area = Rect {
+                    width: area.width - 1,
+                    ..area
+                };
+                (area, Some((scrollbar, bar_area)))
+            }
+            None => (area, None),
+        };
+        state.scrollbar = scrollbar.as_ref().map(|&(_, bar_area)| bar_area);
+
+        let sticky = self.sticky_header.min(len);
+        if sticky == 0 {
+            self.render_with_footer(area, buf, state);
+        } else {
+            self.render_with_header(sticky, area, buf, state);
+        }
+        state.sticky_header = sticky;
+        render_scrollbar(scrollbar, buf, state);
+        if kept_selection.is_some() {
+            state.selected = kept_selection;
+        }
+    }
+}
+
+impl<'a, T: Listable> List<'a, T> {
+    /// Returns a list of the given items that takes the appearance of this
+    /// list, e.g. for the pinned items at the top or the bottom.
+    fn pinned_list(&self, items: Vec<T>) -> Self {
+        List {
+            style: self.style,
+            truncate: self.truncate,
+            overscroll_end: self.overscroll_end,
+            wrap: self.wrap,
+            item_height_range: self.item_height_range,
+            default_item_height: self.default_item_height,
+            start_corner: self.start_corner,
+            reversed: self.reversed,
+            highlight_symbol: self.highlight_symbol,
+            highlight_symbol_style: self.highlight_symbol_style,
+            highlight_symbol_width: self.highlight_symbol_width,
+            highlight_full_width: self.highlight_full_width,
+            highlight_theme: self.highlight_theme,
+            highlight_style: self.highlight_style,
+            separator: self.separator.clone(),
+            separate_all: self.separate_all,
+            separator_height: self.separator_height,
+            spacing: self.spacing,
+            gap_style: self.gap_style,
+            compact: self.compact,
+            highlightable: self.highlightable,
+            ..Self::with_items(Items::Owned(items))
+        }
+    }
+
+    /// Returns the number of rows of the given pinned items. The selected
+    /// item takes its highlighted height, as in the layout of the pinned
+    /// items, so that a selected item that shrinks leaves no gap.
+    fn pinned_height(&self, items: &[T], area: Rect, selected: Option<usize>) -> u16 {
+        let width = area.width - self.symbol_width(items, area.width);
+        let item_heights: Vec<_> = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                self.item_height(item, || match selected {
+                    _ if self.wrap => item.height_for_width(width),
+                    Some(selected) if selected == i => item.highlighted_height(),
+                    _ => item.height(),
+                })
+            })
+            .collect();
+        let (_, gap_heights) = self.gap_heights(items, &item_heights);
+        let height: usize = item_heights.iter().chain(&gap_heights).sum();
+        height.min(area.height as usize) as u16
+    }
+
+    /// Returns the width of the gutter of the highlight symbols, at most the
+    /// given width. The gutter is as wide as the widest symbol, so that the
+    /// items stay aligned, unless the list sets a fixed width.
+    fn symbol_width(&self, items: &[T], width: u16) -> u16 {
+        self.highlight_symbol_width
+            .unwrap_or_else(|| {
+                items
+                    .iter()
+                    .filter_map(Listable::highlight_symbol)
+                    .chain(self.highlight_symbol)
+                    .map(|symbol| Span::raw(symbol).width() as u16)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .min(width)
+    }
+
+    /// Whether the separator is drawn beneath the given item.
+    fn separated(&self, item: &T) -> bool {
+        self.separator.is_some() && (self.separate_all || item.separator_after())
+    }
+
+    /// Returns the rows of the separator beneath each item and the rows of
+    /// the gap beneath each item, i.e. of the separator and of the spacing,
+    /// for the given heights of the items. Hidden items and the visible item
+    /// at the bottom of the layout have no gap.
+    fn gap_heights(&self, items: &[T], item_heights: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        let from_bottom = matches!(self.start_corner, Corner::BottomLeft | Corner::BottomRight);
+        let mut shown = (0..items.len()).filter(|&i| item_heights[i] > 0);
+        let bottom_item = if self.reversed == from_bottom {
+            shown.next_back()
+        } else {
+            shown.next()
+        };
+        let separator_heights: Vec<_> = (0..items.len())
+            .map(|i| {
+                if item_heights[i] > 0 && Some(i) != bottom_item && self.separated(&items[i]) {
+                    self.separator_height
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let gap_heights = (0..items.len())
+            .map(|i| match item_heights[i] {
+                0 => 0,
+                _ if Some(i) == bottom_item => 0,
+                _ => separator_heights[i] + self.spacing as usize,
+            })
+            .collect();
+        (separator_heights, gap_heights)
+    }
+
+    /// Returns the height of the item in the layout. The item is measured by
+    /// the given function, or by its compact height if the list is compact.
+    /// An item with a height of zero takes the default item height, and the
+    /// height is clamped to the range of the item heights.
+    fn item_height(&self, item: &T, measure: impl FnOnce() -> usize) -> usize {
+        let height = if self.compact {
+            item.compact_height()
+        } else {
+            measure()
+        };
+        let height = match height {
+            0 => self.default_item_height,
+            height => height,
+        };
+        let (min_height, max_height) = self.item_height_range;
+        height.max(min_height).min(max_height)
+    }
+
+    /// Renders the pinned items at the top of the area and the other items
+    /// in the rows below them, see [`List::sticky_header`]. The other items
+    /// are rendered like a list of their own, whose indices start after the
+    /// pinned items.
+    fn render_with_header(
+        mut self,
+        sticky: usize,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ListState,
+    ) {
+        let len = self.items.as_slice().len();
+        let header_items = self.items.split_front(sticky);
+        let header_height = self.pinned_height(&header_items, area, state.selected);
+        let header_area = Rect {
+            height: header_height,
+            ..area
+        };
+        let main_area = Rect {
+            y: header_area.bottom(),
+            height: area.height - header_height,
+            ..area
+        };
+        let header = self.pinned_list(header_items);
+        let mut header_state =
+            ListState::default().with_selected(state.selected.filter(|&i| i < sticky));
+        header_state.h_offset = state.h_offset;
+        header_state.selected_items = state.selected_items.clone();
+        header.render_items(header_area, buf, &mut header_state);
+
+        // The scrolling items keep their offset while a pinned item is selected
+        let (selected, detached) = (state.selected, state.detached);
+        let marked = std::mem::take(&mut state.selected_items);
+        let transitions = std::mem::take(&mut state.transitions);
+        let drop_target = state.drop_target;
+        let shift = |i: usize| i.checked_sub(sticky);
+        state.selected = selected.and_then(shift);
+        if selected.is_some_and(|index| index < sticky) {
+            state.detached = true;
+        }
+        state.selected_items = marked.iter().filter_map(|&i| shift(i)).collect();
+        state.transitions = transitions
+            .iter()
+            .filter_map(|(&i, &t)| Some((shift(i)?, t)))
+            .collect();
+        state.drop_target = drop_target.map(|i| i.saturating_sub(sticky));
+        self.render_with_footer(main_area, buf, state);
+
+        // Restore the indices of all items
+        state.selected = selected;
+        if selected.is_some_and(|index| index < sticky) {
+            state.detached = detached;
+        }
+        state.selected_items = marked;
+        let main_transitions = std::mem::take(&mut state.transitions);
+        state.transitions = transitions
+            .into_iter()
+            .filter(|&(i, _)| i < sticky)
+            .chain(main_transitions.into_iter().map(|(i, t)| (i + sticky, t)))
+            .collect();
+        state.drop_target = drop_target;
+        state.num_elements = len;
+        // The pinned items are skipped by the navigation
+        state.unselectable = (0..sticky)
+            .chain(state.unselectable.iter().map(|i| i + sticky))
+            .collect();
+        state.hidden = (0..sticky)
+            .chain(state.hidden.iter().map(|i| i + sticky))
+            .collect();
+        state.visible_range = state.visible_range.start + sticky..state.visible_range.end + sticky;
+        for item in &mut state.layout {
+            item.index += sticky;
+        }
+        for (_, _, index) in state.indicators.iter_mut().flatten() {
+            *index += sticky;
+        }
+        state.layout.splice(0..0, header_state.layout);
+    }
+
+    /// Renders the frozen items at the bottom of the area and the other items
+    /// in the rows above them, see [`List::frozen_bottom`].
+    fn render_with_footer(mut self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let len = self.items.as_slice().len();
+        let frozen = self.frozen_bottom.min(len);
+        if frozen == 0 {
+            self.render_items(area, buf, state);
+            return;
+        }
+
+        // The frozen items are rendered as a list of their own at the bottom
+        // of the area, and the other items scroll in the rows above them.
+        let start = len - frozen;
+        let footer_items = self.items.split_off(start);
+        let footer_selected = state.selected.and_then(|i| i.checked_sub(start));
+        let footer_height = self.pinned_height(&footer_items, area, footer_selected);
+        let main_area = Rect {
+            height: area.height - footer_height,
+            ..area
+        };
+        let footer_area = Rect {
+            y: main_area.bottom(),
+            height: footer_height,
+            ..area
+        };
+        let footer = self.pinned_list(footer_items);
+        let mut footer_state = ListState::default().with_selected(footer_selected);
+        footer_state.h_offset = state.h_offset;
+        footer.render_items(footer_area, buf, &mut footer_state);
+
+        // The scrolling items keep their offset while a frozen item is selected
+        let (selected, detached) = (state.selected, state.detached);
+        if selected.is_some_and(|index| index >= start) {
+            state.selected = None;
+            state.detached = true;
+        }
+        self.render_items(main_area, buf, state);
+        state.selected = selected;
+        state.detached = detached;
+        state.num_elements = len;
+        let unselectable = footer_state.unselectable.iter().map(|i| i + start);
+        state.unselectable.extend(unselectable);
+        let hidden = footer_state.hidden.iter().map(|i| i + start);
+        state.hidden.extend(hidden);
+        let layout = footer_state.layout.iter().map(|&item| ItemLayout {
+            index: item.index + start,
+            ..item
+        });
+        state.layout.extend(layout);
+    }
+
+    /// Renders the items into the area inside the block.
+    fn render_items(mut self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        if self.direction == ListDirection::Horizontal && !self.items.as_slice().is_empty() {
+            self.render_horizontal(area, buf, state);
+            return;
+        }
+        if self.flow_columns > 1 && !self.items.as_slice().is_empty() {
+            self.render_flow_columns(area, buf, state);
+            return;
+        }
+        let items = std::mem::replace(&mut self.items, Items::Owned(Vec::new()));
+        state.set_num_elements(items.as_slice().len());
+
+        // Remember the viewport height
+        let previous_height = state.viewport_length.replace(area.height);
+        state.viewport_height = Some(area.height);
+
+        // List is empty
+        if items.as_slice().is_empty() {
+            buf.set_style(area, self.style);
+            state.heights.clear();
+            state.unselectable.clear();
+            state.hidden.clear();
+            state.visible_items = 0;
+            state.visible_range = 0..0;
+            state.layout.clear();
+            state.indicators = [None, None];
+            #[cfg(feature = "metrics")]
+            {
+                state.render_stats = crate::RenderStats::default();
+            }
+            return;
+        }
+        #[cfg(feature = "metrics")]
+        let layout_start = std::time::Instant::now();
+
+        // Remember which items cannot be selected
+        state.unselectable = (0..items.as_slice().len())
+            .filter(|&i| !items.as_slice()[i].selectable())
+            .chain(
+                state
+                    .transitions
+                    .iter()
+                    .filter(|(_, t)| t.leaving)
+                    .map(|(i, _)| *i),
+            )
+            .collect();
+        let transitions = state.transitions.clone();
+
+        // The selected item is highlighted once it is rendered
+        let selected = state.selected();
+        let item_at = |i: usize| &items.as_slice()[i];
+
+        // The drop indicator is drawn in the gap between the items at the drop
+        // target. If the target is above the topmost item of the list, a row at
+        // the top of the area is reserved for it.
+        let len = items.as_slice().len();
+        let from_bottom = matches!(self.start_corner, Corner::BottomLeft | Corner::BottomRight);
+        let downward = self.reversed == from_bottom;
+        let drop_target = state
+            .drop_target
+            .filter(|_| self.drop_indicator.is_some())
+            .map(|target| target.min(len));
+        let drop_on_top =
+            drop_target.is_some_and(|target| target == if downward { 0 } else { len });
+        let full_area = area;
+        let area = if drop_on_top && area.height > 1 {
+            buf.set_style(Rect { height: 1, ..area }, self.style);
+            buf.set_style(Rect { height: 1, ..area }, self.gap_style);
+            Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            }
+        } else {
+            area
+        };
+
+        // The highlight symbol is drawn in a gutter left of the items
+        let symbol_width = self.symbol_width(items.as_slice(), area.width);
+
+        // Use the full width, except for the gutter
+        let width = area.width - symbol_width;
+
+        // Maximum height
+        let max_height = area.height as usize;
+
+        // The horizontal position of the items
+        let x = area.left() + symbol_width;
+
+        // The items are laid out from the start corner of the list. The position
+        // of an item in the layout differs from its index if the list is reversed.
+        let last = items.as_slice().len() - 1;
+        let reversed = self.reversed;
+        let item_of = |position: usize| if reversed { last - position } else { position };
+
+        // The truncation of the top and the bottom of the viewport applies to
+        // the end and the start of the layout if the list starts at the bottom.
+        let truncate = if from_bottom {
+            Truncate {
+                top: self.truncate.bottom,
+                bottom: self.truncate.top,
+            }
+        } else {
+            self.truncate
+        };
+
+        // The heights that were measured for an assumed width are reused if the
+        // width matches, except for the height of the selected item.
+        let cached_heights = self
+            .assumed_heights
+            .as_ref()
+            .filter(|(assumed, heights)| *assumed == width && heights.len() == last + 1)
+            .map(|(_, heights)| heights);
+        // The heights of wrapped items with a content hash are kept in the state
+        // for the next render. Only the heights for the current width are kept.
+        let previous_cache = std::mem::take(&mut state.height_cache);
+        let mut height_cache = HashMap::new();
+        let mut height_of = |i: usize| {
+            let height = self.item_height(item_at(i), || match cached_heights {
+                Some(heights) if self.wrap && selected != Some(i) => heights[i],
+                _ if self.wrap => match item_at(i).content_hash() {
+                    Some(hash) if selected != Some(i) => {
+                        *height_cache.entry((width, hash)).or_insert_with(|| {
+                            previous_cache
+                                .get(&(width, hash))
+                                .copied()
+                                .unwrap_or_else(|| item_at(i).height_for_width(width))
+                        })
+                    }
+                    _ => item_at(i).height_for_width(width),
+                },
+                _ if selected == Some(i) => item_at(i).highlighted_height(),
+                _ => item_at(i).height(),
+            });
+            match transitions.get(&i) {
+                // Removed items collapse once they faded out
+                Some(t) if t.leaving && t.frames == 0 => 0,
+                _ => height,
+            }
+        };
+        let item_heights: Vec<_> = (0..=last).map(&mut height_of).collect();
+        state.height_cache = height_cache;
+
+        // Items with a height of zero are hidden. They are skipped by the
+        // navigation and cannot be selected.
+        state.hidden = (0..=last).filter(|&i| item_heights[i] == 0).collect();
+        state.unselectable.extend(state.hidden.iter().copied());
+
+        // Split out the heights of the items for efficiency as we have to iterate
+        // over the heights back and forth to determine which widget is shown on the
+        // viewport. Each item occupies its own height and the rows of the separator
+        // and the spacing beneath it.
+        let (separator_heights, mut gap_heights) =
+            self.gap_heights(items.as_slice(), &item_heights);
+
+        // The gap beneath the item above the drop target takes the drop indicator.
+        // A row is reserved for it if the item has no gap.
+        let drop_gap = drop_target.filter(|_| !drop_on_top).and_then(|target| {
+            if downward {
+                (0..target).rev().find(|&i| item_heights[i] > 0)
+            } else {
+                (target..=last).find(|&i| item_heights[i] > 0)
+            }
+        });
+        if let Some(i) = drop_gap {
+            gap_heights[i] = gap_heights[i].max(1);
+        }
+        let raw_heights: Vec<_> = (0..=last)
+            .map(|position| {
+                let i = item_of(position);
+                item_heights[i] + gap_heights[i]
+            })
+            .collect();
+
+        // If the viewport grew since the last render, reveal the items above
+        // the viewport instead of leaving blank rows below the last item.
+        let grew = previous_height.map_or(true, |height| area.height > height);
+        if grew && !self.overscroll_end {
+            state.fill_view_port(&raw_heights, max_height);
+        }
+
+        // Determine which widgets to show on the viewport and how much space they
+        // get assigned to. The number of elements in `view_heights` is less than
+        // the number of elements in `raw_heights` if not all widgets are shown
+        // on the viewport.
+        state.reversed = reversed;
+        state.scroll_strategy = self.scroll_strategy;
+        state.programmatic_scroll_strategy = self.programmatic_scroll_strategy;
+        let view_heights = if state.detached || !self.auto_scroll {
+            // The offset was scrolled explicitly or does not follow the selection.
+            // The first item may be cut off by the rows that were dragged.
+            let max_offset = ListState::max_offset(&raw_heights, max_height, self.overscroll_end);
+            if state.offset > max_offset {
+                state.offset = max_offset;
+                state.top_clip = 0;
+            }
+            let content: usize = raw_heights[state.offset..].iter().sum();
+            let max_clip = if self.overscroll_end {
+                usize::MAX
+            } else {
+                content.saturating_sub(max_height)
+            };
+            state.clamp_top_clip(raw_heights[state.offset], max_clip);
+            let mut view_heights = ListState::view_heights(
+                &raw_heights,
+                state.offset,
+                max_height + state.top_clip,
+                truncate.bottom,
+            );
+            if let Some(first) = view_heights.first_mut() {
+                *first -= state.top_clip.min(*first);
+            }
+            view_heights
+        } else {
+            // The items may have been removed since the last render
+            let max_offset = ListState::max_offset(&raw_heights, max_height, self.overscroll_end);
+            let previous_offset = state.offset.min(max_offset);
+            let strategy = state.active_scroll_strategy();
+            let view_heights = state.update_view_port(&raw_heights, max_height, truncate, strategy);
+            let view_heights = state.apply_scroll_padding(
+                &raw_heights,
+                max_height,
+                truncate,
+                self.scroll_padding,
+                view_heights,
+            );
+            if let Some(offset) = state.scroll_step(previous_offset) {
+                ListState::view_heights(&raw_heights, offset, max_height, truncate.bottom)
+            } else {
+                view_heights
+            }
+        };
+        state.truncate = truncate;
+        state.visible_items = view_heights
+            .iter()
+            .zip(raw_heights.iter().skip(state.offset))
+            .enumerate()
+            .filter(|(k, (view_height, height))| {
+                view_height == height && (*k > 0 || state.top_clip == 0)
+            })
+            .count();
+        state.heights = raw_heights;
+        #[cfg(feature = "metrics")]
+        {
+            state.render_stats = crate::RenderStats {
+                items_rendered: view_heights.iter().filter(|&&height| height > 0).count(),
+                items_measured: state.heights.len(),
+                layout_micros: layout_start.elapsed().as_micros() as u64,
+            };
+        }
+
+        // Iterate over the items that are shown on the viewport
+        let offset = state.offset;
+        let range = if reversed {
+            last + 1 - (offset + view_heights.len())..last + 1 - offset
+        } else {
+            offset..offset + view_heights.len()
+        };
+        let hidden = view_heights.iter().rev().take_while(|&&h| h == 0).count();
+        state.visible_range = if reversed {
+            range.start + hidden..range.end
+        } else {
+            range.start..range.end - hidden
+        };
+        state.layout.clear();
+        state.indicators = [None, None];
+        let mut view_items = items.into_range(range.clone());
+        if reversed {
+            view_items.reverse();
+        }
+        let view_items = view_items.into_iter().zip(view_heights);
+        let mut rows = 0;
+        let mut drop_row = None;
+        for (k, (mut item, height)) in view_items.enumerate() {
+            if self.compact {
+                item = item.compact();
+            }
+            let position = offset + k;
+            let i = item_of(position);
+            let full_height = state.heights[position];
+            if full_height == 0 {
+                continue;
+            }
+            let item_height = full_height - gap_heights[i];
+
+            // The rows of the first item in the layout may be clipped at the start,
+            // the rows of the last item at the end of the layout.
+            let start_clip = if k == 0 { state.top_clip } else { 0 };
+            let end_clip = full_height.saturating_sub(height + start_clip);
+            let (hidden_top, hidden_bottom) = if from_bottom {
+                (end_clip, start_clip)
+            } else {
+                (start_clip, end_clip)
+            };
+            let y = if from_bottom {
+                area.bottom().saturating_sub((rows + height) as u16)
+            } else {
+                area.top() + rows as u16
+            };
+            rows += height;
+
+            // The item takes the upper rows and the separator the lower rows. The
+            // rows of the item beyond its fill height are left to the gap.
+            let fill_height = item
+                .fill_height()
+                .map_or(item_height, |h| h.min(item_height));
+            let visible_height = fill_height
+                .min(full_height.saturating_sub(hidden_bottom))
+                .saturating_sub(hidden_top);
+            // Opaque items paint their area themselves. The rows of the
+            // separator beneath the item take the gap style.
+            let slot = Rect::new(area.left(), y, area.width, height as u16);
+            if height > 0 {
+                state.layout.push(ItemLayout {
+                    index: i,
+                    rect: slot,
+                    truncated_top: hidden_top,
+                    truncated_bottom: hidden_bottom,
+                });
+            }
+            let below = Rect {
+                y: y + visible_height as u16,
+                height: (height - visible_height) as u16,
+                ..slot
+            };
+            if item.opaque() {
+                let gutter = Rect {
+                    width: symbol_width,
+                    ..slot
+                };
+                buf.set_style(gutter, self.style);
+                buf.set_style(below, self.style);
+            } else {
+                buf.set_style(slot, self.style);
+            }
+            buf.set_style(below, self.gap_style);
+
+            let context = ItemContext {
+                index: i,
+                is_first: i == 0,
+                is_last: i == last,
+                wrap: self.wrap,
+                h_offset: state.h_offset,
+            };
+            let mut item = item.context(&context);
+            if self.highlightable && state.selected_items.contains(&i) {
+                item = item.mark();
+            }
+            if hidden_top > 0 {
+                item = item.truncate_top(hidden_top.min(item_height));
+            }
+            let cut_bottom = hidden_bottom.saturating_sub(gap_heights[i]);
+            if cut_bottom > 0 {
+                item = item.truncate_bottom(cut_bottom.min(item_height));
+            }
+            if selected == Some(i) && visible_height > 0 && area.width > 0 && symbol_width > 0 {
+                if let Some(symbol) = item.highlight_symbol().or(self.highlight_symbol) {
+                    let style = self.highlight_symbol_style;
+                    buf.set_stringn(area.left(), y, symbol, symbol_width as usize, style);
+                }
+            }
+            let item_area = Rect::new(x, y, width, visible_height as u16);
+            buf.set_style(item_area, item.item_style());
+            if selected == Some(i) {
+                item.render_selected(item_area, buf, &self.highlight_theme);
+            } else {
+                item.render(item_area, buf);
+            }
+            if selected == Some(i) {
+                buf.set_style(item_area, self.highlight_style);
+            }
+            if transitions.get(&i).is_some_and(|t| t.frames > 0) {
+                buf.set_style(item_area, Style::default().add_modifier(Modifier::DIM));
+            }
+            if let Some(bg) = self.highlight_theme.style.bg {
+                if self.highlight_full_width && selected == Some(i) {
+                    let row = Rect::new(area.left(), y, area.width, visible_height as u16);
+                    replace_bg(row, buf, self.style.bg.unwrap_or(Color::Reset), bg);
+                }
+            }
+            if let Some(separator) = &self.separator {
+                let rows = hidden_top..full_height.saturating_sub(hidden_bottom);
+                if separator_heights[i] > 0 && rows.contains(&item_height) {
+                    let y = y + (item_height - hidden_top) as u16;
+                    buf.set_line(area.left(), y, separator, area.width);
+                }
+            }
+            if drop_gap == Some(i) {
+                let rows = hidden_top..full_height.saturating_sub(hidden_bottom);
+                if rows.contains(&item_height) {
+                    drop_row = Some(y + (item_height - hidden_top) as u16);
+                }
+            }
+        }
+
+        // Style the rows that are not taken by items
+        let rows = (rows as u16).min(area.height);
+        let empty = if from_bottom {
+            Rect {
+                height: area.height - rows,
+                ..area
+            }
+        } else {
+            Rect {
+                y: area.top() + rows,
+                height: area.height - rows,
+                ..area
+            }
+        };
+        buf.set_style(empty, self.style);
+        buf.set_style(empty, self.gap_style);
+
+        // Draw the insertion line in the gap above the drop target, or in the
+        // reserved row above the topmost item if it is fully shown
+        if let Some(indicator) = &self.drop_indicator {
+            let topmost = if downward { 0 } else { last };
+            let y = drop_row.or_else(|| {
+                let item = state.layout.iter().find(|item| item.index == topmost)?;
+                let shown = drop_on_top && item.truncated_top == 0;
+                (shown && item.rect.top() > full_area.top()).then(|| item.rect.top() - 1)
+            });
+            if let Some(y) = y {
+                buf.set_line(full_area.left(), y, indicator, full_area.width);
+            }
+        }
+
+        // Draw the numbers of the layout for debugging
+        if self.debug_overlay && area.width > 0 && area.height > 0 {
+            let mut text = format!(
+                "off:{} vh:{} sel:{}",
+                offset,
+                area.height,
+                selected.map_or_else(|| "-".to_string(), |i| i.to_string())
+            );
+            for item in &state.layout {
+                text.push_str(&format!(" [{}:{}]", item.index, item.rect.height));
+            }
+            let width = (Span::raw(text.as_str()).width() as u16).min(area.width);
+            let x = area.right() - width;
+            buf.set_stringn(x, area.top(), text, width as usize, Style::default());
+        }
+
+        // Indicate that items are hidden above or below the viewport
+        if self.scroll_indicators && area.width > 0 && area.height > 0 {
+            let content: usize = state.heights[offset..].iter().sum();
+            let hidden_start = offset > 0 || state.top_clip > 0;
+            let hidden_end = content - state.top_clip > max_height;
+            let (hidden_top, hidden_bottom) = if from_bottom {
+                (hidden_end, hidden_start)
+            } else {
+                (hidden_start, hidden_end)
+            };
+            let top_item = if reversed == from_bottom { 0 } else { last };
+            let x = area.right() - 1;
+            if hidden_top {
+                buf.set_string(x, area.top(), "⤒", self.style);
+                state.indicators[0] = Some((x, area.top(), top_item));
+            }
+            if hidden_bottom {
+                buf.set_string(x, area.bottom() - 1, "⤓", self.style);
+                state.indicators[1] = Some((x, area.bottom() - 1, last - top_item));
+            }
+        }
+
+        // Advance the animations of inserted and removed items. Removed items
+        // are kept as collapsed until the next update.
+        for transition in state.transitions.values_mut() {
+            transition.frames = transition.frames.saturating_sub(1);
+        }
+        state.transitions.retain(|_, t| t.leaving || t.frames > 0);
+    }
+}
+
+/// Renders the scrollbar into its area, see [`ListState::scrollbar_state`].
+fn render_scrollbar(scrollbar: Option<(Scrollbar, Rect)>, buf: &mut Buffer, state: &ListState) {
+    let Some((scrollbar, area)) = scrollbar else {
+        return;
+    };
+    scrollbar.render(area, buf, &mut state.scrollbar_state());
+}
+
+/// Sets the style of the cells in the area that are outside of the inner area.
+fn set_style_outside(area: Rect, inner: Rect, buf: &mut Buffer, style: Style) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let inside = (inner.left()..inner.right()).contains(&x)
+                && (inner.top()..inner.bottom()).contains(&y);
+            if !inside {
+                buf.get_mut(x, y).set_style(style);
+            }
+        }
+    }
+}
+
+impl<'a, T: Listable> List<'a, T> {
+    /// Renders the items into columns that are filled one after another,
+    /// see [`List::flow_columns`]. The list must not be empty.
+    fn render_flow_columns(mut self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let items = std::mem::replace(&mut self.items, Items::Owned(Vec::new()));
+        let len = items.as_slice().len();
+        state.set_num_elements(len);
+        state.viewport_height = Some(area.height);
+        state.viewport_length = Some(area.height);
+        state.reversed = false;
+        state.unselectable = (0..len)
+            .filter(|&i| !items.as_slice()[i].selectable())
+            .collect();
+        buf.set_style(area, self.style);
+
+        // The columns share the width of the area, but are at least one cell wide
+        let columns = self.flow_columns.clamp(1, area.width.max(1) as usize) as u16;
+        let column_x = |k: usize| area.left() + (area.width as usize * k / columns as usize) as u16;
+        let column_width = area.width / columns;
+        let max_height = area.height as usize;
+
+        // Measure the items
+        let selected = state.selected();
+        state.heights = (0..len)
+            .map(|i| {
+                let item = &items.as_slice()[i];
+                self.item_height(item, || match selected {
+                    _ if self.wrap => item.height_for_width(column_width),
+                    Some(selected) if selected == i => item.highlighted_height(),
+                    _ => item.height(),
+                })
+            })
+            .collect();
+        state.hidden = (0..len).filter(|&i| state.heights[i] == 0).collect();
+        state.unselectable.extend(state.hidden.iter().copied());
+
+        // Fill the columns with the items. An item that is larger than the
+        // viewport takes a column of its own and is cut off at the bottom.
+        let mut starts = vec![0];
+        let mut rows = 0;
+        for (i, &height) in state.heights.iter().enumerate() {
+            if rows > 0 && rows + height > max_height {
+                starts.push(i);
+                rows = 0;
+            }
+            rows += height;
+        }
+
+        // Scroll by all columns at once, so that the selection is shown
+        let target = selected.unwrap_or(state.offset).min(len - 1);
+        let column = starts.partition_point(|&start| start <= target) - 1;
+        let first_column = column - column % columns as usize;
+        let start = starts[first_column];
+        let end = starts
+            .get(first_column + columns as usize)
+            .copied()
+            .unwrap_or(len);
+        state.offset = start;
+        state.top_clip = 0;
+        state.detached = false;
+        state.scrolling = false;
+        state.visible_items = end - start;
+        state.visible_range = start..end;
+        state.layout.clear();
+        state.indicators = [None, None];
+
+        let (mut k, mut y) = (first_column, area.top());
+        for (i, mut item) in (start..end).zip(items.into_range(start..end)) {
+            if self.compact {
+                item = item.compact();
+            }
+            if starts.get(k + 1) == Some(&i) {
+                k += 1;
+                y = area.top();
+            }
+            let height = (state.heights[i] as u16).min(area.bottom() - y);
+            let x = column_x(k - first_column);
+            let item_area = Rect::new(x, y, column_width, height);
+            y += height;
+            if height > 0 {
+                state.layout.push(ItemLayout {
+                    index: i,
+                    rect: item_area,
+                    truncated_top: 0,
+                    truncated_bottom: state.heights[i] - height as usize,
+                });
+            }
+
+            let context = ItemContext {
+                index: i,
+                is_first: i == 0,
+                is_last: i == len - 1,
+                wrap: self.wrap,
+                h_offset: state.h_offset,
+            };
+            let mut item = item.context(&context);
+            if self.highlightable && state.selected_items.contains(&i) {
+                item = item.mark();
+            }
+            if state.heights[i] > height as usize {
+                item = item.truncate_bottom(state.heights[i] - height as usize);
+            }
+            buf.set_style(item_area, item.item_style());
+            if selected == Some(i) {
+                item.render_selected(item_area, buf, &self.highlight_theme);
+                buf.set_style(item_area, self.highlight_style);
+            } else {
+                item.render(item_area, buf);
+            }
+        }
+    }
+
+    /// Renders the items from left to right, see [`List::direction`]. The
+    /// offset of the state counts items and the heights of the state are
+    /// the widths of the items.
+    fn render_horizontal(self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let items = self.items;
+        let len = items.as_slice().len();
+        state.set_num_elements(len);
+        state.viewport_height = Some(area.height);
+        state.viewport_length = Some(area.width);
+        state.reversed = false;
+        state.scroll_strategy = self.scroll_strategy;
+        state.programmatic_scroll_strategy = self.programmatic_scroll_strategy;
+        state.unselectable = (0..len)
+            .filter(|&i| !items.as_slice()[i].selectable())
+            .collect();
+        buf.set_style(area, self.style);
+
+        // Measure the items. Items with a width of zero are hidden.
+        let selected = state.selected();
+        let max_width = area.width as usize;
+        state.heights = items
+            .as_slice()
+            .iter()
+            .map(|item| item.width().unwrap_or(max_width))
+            .collect();
+        state.hidden = (0..len).filter(|&i| state.heights[i] == 0).collect();
+        state.unselectable.extend(state.hidden.iter().copied());
+
+        // The first item on the viewport is never cut off at the left edge
+        let truncate = Truncate {
+            top: false,
+            bottom: self.truncate.bottom,
+        };
+        let widths = std::mem::take(&mut state.heights);
+        let view_widths = if state.detached || !self.auto_scroll {
+            let max_offset = ListState::max_offset(&widths, max_width, self.overscroll_end);
+            state.offset = state.offset.min(max_offset);
+            ListState::view_heights(&widths, state.offset, max_width, truncate.bottom)
+        } else {
+            let strategy = state.active_scroll_strategy();
+            state.update_view_port(&widths, max_width, truncate, strategy)
+        };
+        state.heights = widths;
+        state.top_clip = 0;
+        state.scrolling = false;
+        let offset = state.offset;
+        state.visible_items = view_widths
+            .iter()
+            .zip(&state.heights[offset..])
+            .filter(|(view_width, width)| view_width == width)
+            .count();
+        state.visible_range = offset..offset + view_widths.len();
+        state.layout.clear();
+        state.indicators = [None, None];
+
+        let mut x = area.left();
+        let range = offset..offset + view_widths.len();
+        for ((i, item), width) in range.clone().zip(items.into_range(range)).zip(view_widths) {
+            if width == 0 {
+                continue;
+            }
+            let item_area = Rect::new(x, area.top(), width as u16, area.height);
+            x += width as u16;
+            state.layout.push(ItemLayout {
+                index: i,
+                rect: item_area,
+                truncated_top: 0,
+                truncated_bottom: state.heights[i] - width,
+            });
+
+            let context = ItemContext {
+                index: i,
+                is_first: i == 0,
+                is_last: i == len - 1,
+                wrap: self.wrap,
+                h_offset: state.h_offset,
+            };
+            let mut item = item.context(&context);
+            if self.highlightable && state.selected_items.contains(&i) {
+                item = item.mark();
+            }
+            buf.set_style(item_area, item.item_style());
+            if selected == Some(i) {
+                item.render_selected(item_area, buf, &self.highlight_theme);
+                buf.set_style(item_area, self.highlight_style);
+            } else {
+                item.render(item_area, buf);
+            }
+        }
+    }
+}
+
+/// Sets the background of the cells in the area that have the given background.
+fn replace_bg(area: Rect, buf: &mut Buffer, from: Color, to: Color) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = buf.get_mut(x, y);
+            if cell.bg == from {
+                cell.set_bg(to);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ListElement, ListEvent};
+    use ratatui::widgets::{Borders, Paragraph, ScrollbarState, Wrap};
+
+    #[derive(Debug, Clone)]
+    struct TestItem {
+        text: &'static str,
+        height: usize,
+        separator_after: bool,
+        highlighted: bool,
+        marked: bool,
+        style: Style,
+    }
+
+    impl TestItem {
+        fn new(text: &'static str, height: usize) -> Self {
+            Self {
+                text,
+                height,
+                separator_after: false,
+                highlighted: false,
+                marked: false,
+                style: Style::default(),
+            }
+        }
+
+        fn separated(mut self) -> Self {
+            self.separator_after = true;
+            self
+        }
+    }
+
+    impl Listable for TestItem {
+        fn height(&self) -> usize {
+            self.height
+        }
+
+        fn highlight(mut self) -> Self {
+            self.highlighted = true;
+            self
+        }
+
+        fn highlight_with(mut self, theme: &HighlightTheme) -> Self {
+            self.style = theme.style;
+            self.highlight()
+        }
+
+        fn mark(mut self) -> Self {
+            self.marked = true;
+            self
+        }
+
+        fn separator_after(&self) -> bool {
+            self.separator_after
+        }
+    }
+
+    impl Keyed for TestItem {
+        type Key = &'static str;
+
+        fn key(&self) -> Self::Key {
+            self.text
+        }
+    }
+
+    impl Widget for TestItem {
+        fn render(self, area: Rect, buf: &mut Buffer) {
+            let text = match (self.highlighted, self.marked) {
+                (true, true) => format!(">*{}", self.text),
+                (true, false) => format!(">{}", self.text),
+                (false, true) => format!("*{}", self.text),
+                (false, false) => self.text.to_string(),
+            };
+            Paragraph::new(text).style(self.style).render(area, buf);
+        }
+    }
+
+    fn render<T: Listable>(
+        list: List<T>,
+        width: u16,
+        height: u16,
+        state: &mut ListState,
+    ) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        list.render(area, &mut buf, state);
+        buf
+    }
+
+    #[test]
+    fn separator_after_selected_items() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1).separated(),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1).separated(),
+        ];
+        let list = List::new(items).separator("---");
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 3, 5, &mut state);
+
+        // then
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["a  ", "---", "b  ", "c  ", "   "])
+        );
+    }
+
+    #[test]
+    fn separator_between_items() {
+        // given
+        #[derive(Clone)]
+        struct ShortItem(&'static str);
+
+        impl Listable for ShortItem {
+            fn height(&self) -> usize {
+                1
+            }
+        }
+
+        impl Widget for ShortItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                Paragraph::new(self.0).render(area, buf);
+            }
+        }
+        let items = vec![ShortItem("a"), ShortItem("b")];
+        let list = List::new(items).separator("---");
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list.clone(), 3, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", "b  ", "   ", "   "]));
+
+        // when the separator is drawn between all items
+        let buf = render(list.separate_all(true), 3, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", "---", "b  ", "   "]));
+    }
+
+    #[test]
+    fn separator_height_is_reserved() {
+        // given
+        let items = vec![TestItem::new("a", 1).separated(), TestItem::new("b", 1)];
+        let list = List::new(items).separator("---").separator_height(2);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 3, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", "---", "   ", "b  "]));
+    }
+
+    #[test]
+    fn render_borrowed_items() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ];
+        let list = List::from_slice(&items);
+        let mut state = ListState::default();
+        state.select(Some(2));
+
+        // when
+        let buf = render(list, 3, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["b  ", ">c "]));
+        assert!(items.iter().all(|item| !item.highlighted));
+    }
+
+    #[test]
+    fn render_owned_items() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list, 3, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", ">b ", "c  "]));
+    }
+
+    #[test]
+    fn highlight_with_theme() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let theme = HighlightTheme::new(Style::default().bg(Color::Blue));
+        let list = List::new(items).highlight_theme(theme);
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["a ", ">b"]);
+        expected.set_style(Rect::new(0, 1, 2, 1), Style::default().bg(Color::Blue));
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn selection_stays_visible_on_resize() {
+        // given
+        let items: Vec<_> = ["a", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(5));
+        render(List::new(items.clone()), 2, 10, &mut state);
+
+        // when
+        let buf = render(List::new(items), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["c ", "d ", "e ", ">f"]));
+    }
+
+    #[test]
+    fn fill_viewport_on_resize() {
+        // given
+        let items: Vec<_> = ["a", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(5));
+        render(List::new(items.clone()), 2, 2, &mut state);
+
+        // when
+        let buf = render(List::new(items), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["c ", "d ", "e ", ">f"]));
+    }
+
+    #[derive(Debug, Clone)]
+    struct WrapItem {
+        text: &'static str,
+        wrap: bool,
+    }
+
+    impl Listable for WrapItem {
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn height_for_width(&self, width: u16) -> usize {
+            (self.text.len() + width as usize - 1) / width as usize
+        }
+
+        fn context(mut self, context: &ItemContext) -> Self {
+            self.wrap = context.wrap;
+            self
+        }
+    }
+
+    impl Widget for WrapItem {
+        fn render(self, area: Rect, buf: &mut Buffer) {
+            let mut paragraph = Paragraph::new(self.text);
+            if self.wrap {
+                paragraph = paragraph.wrap(Wrap { trim: false });
+            }
+            paragraph.render(area, buf);
+        }
+    }
+
+    #[test]
+    fn context_first_and_last() {
+        // given
+        #[derive(Clone)]
+        struct BorderItem(ItemContext);
+
+        impl Listable for BorderItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn context(self, context: &ItemContext) -> Self {
+                Self(*context)
+            }
+        }
+
+        impl Widget for BorderItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                let symbol = match (self.0.is_first, self.0.is_last) {
+                    (true, _) => "┌",
+                    (_, true) => "└",
+                    _ => "│",
+                };
+                buf.set_string(area.x, area.y, symbol, Style::default());
+            }
+        }
+        let items = vec![BorderItem(ItemContext::default()); 4];
+        let mut state = ListState::default();
+        state.select(Some(3));
+
+        // when
+        let buf = render(List::new(items), 1, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["│", "└"]));
+    }
+
+    #[test]
+    fn horizontal_offset() {
+        // given
+        #[derive(Clone)]
+        struct RowItem(&'static str, u16);
+
+        impl Listable for RowItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn context(mut self, context: &ItemContext) -> Self {
+                self.1 = context.h_offset;
+                self
+            }
+        }
+
+        impl Widget for RowItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                Paragraph::new(self.0).scroll((0, self.1)).render(area, buf);
+            }
+        }
+        let items = vec![RowItem("abcdef", 0), RowItem("ghijkl", 0)];
+        let mut state = ListState::default();
+
+        // when
+        state.scroll_right(3);
+        state.scroll_left(1);
+        let buf = render(List::new(items), 3, 2, &mut state);
+
+        // then
+        assert_eq!(state.h_offset(), 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["cde", "ijk"]));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn last_render_stats() {
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ];
+        let mut state = ListState::default();
+        render(List::new(items), 3, 2, &mut state);
+        let stats = state.last_render_stats();
+        assert_eq!(stats.items_measured, 3);
+        assert_eq!(stats.items_rendered, 2);
+
+        render(List::<TestItem>::new(vec![]), 3, 2, &mut state);
+        assert_eq!(state.last_render_stats(), crate::RenderStats::default());
+    }
+
+    #[test]
+    fn selected_overrides_state() {
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ];
+        let mut state = ListState::default();
+        state.select(Some(0));
+        state.scroll_down(1);
+
+        let buf = render(List::new(items).selected(Some(2)), 2, 2, &mut state);
+
+        assert_eq!(state.selected(), Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", ">c"]));
+    }
+
+    #[test]
+    fn selected_keeps_scrolling() {
+        // given
+        let items: Vec<_> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let list = List::new(items).selected(Some(0));
+        let mut state = ListState::default();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        state.set_event_sink(move |event| sink.lock().unwrap().push(event));
+        render(list.clone(), 2, 2, &mut state);
+
+        // when
+        state.scroll_down(2);
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(buf, Buffer::with_lines(vec!["c ", "d "]));
+        assert_eq!(*events.lock().unwrap(), vec![ListEvent::Scrolled(2)]);
+    }
+
+    #[test]
+    fn visible_items() {
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 2),
+            TestItem::new("c", 1),
+            TestItem::new("d", 1),
+        ];
+        let mut state = ListState::default();
+        render(List::new(items.clone()), 2, 4, &mut state);
+        assert_eq!(state.visible_items, 3);
+
+        state.select(Some(3));
+        render(List::new(items), 2, 4, &mut state);
+        assert_eq!(state.visible_items, 3);
+    }
+
+    #[test]
+    fn clamp_selection() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let clamped = Rc::new(std::cell::Cell::new(None));
+        let on_clamp = Rc::clone(&clamped);
+        let list = List::new(items).on_clamp(move |old, new| on_clamp.set(Some((old, new))));
+        let mut state = ListState::default();
+        state.select(Some(999));
+
+        // when
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(1));
+        assert_eq!(clamped.get(), Some((999, 1)));
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", ">b"]));
+    }
+
+    #[test]
+    fn selection_visible_on_first_render() {
+        // given
+        let items: Vec<_> = (0..100).map(|_| TestItem::new("x", 1)).collect();
+        let mut state = ListState::default().with_selected(Some(50));
+
+        // when
+        let buf = render(List::new(items), 2, 3, &mut state);
+
+        // then
+        assert_eq!(state.offset, 48);
+        assert_eq!(buf, Buffer::with_lines(vec!["x ", "x ", ">x"]));
+    }
+
+    #[test]
+    fn clamp_item_height() {
+        // given
+        let items = vec![
+            TestItem::new("a", 0),
+            TestItem::new("b", 5),
+            TestItem::new("c", 1),
+        ];
+        let list = List::new(items).clamp_item_height(1, 2);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.heights, vec![1, 2, 1]);
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "b ", "  ", "c "]));
+    }
+
+    #[test]
+    fn style_cascade() {
+        // given
+        #[derive(Clone)]
+        struct StyledItem;
+
+        impl Listable for StyledItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn item_style(&self) -> Style {
+                Style::default().fg(Color::Red).bg(Color::Blue)
+            }
+        }
+
+        impl Widget for StyledItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                buf.set_string(area.x, area.y, "a", Style::default().bg(Color::Green));
+            }
+        }
+        let list = List::new(vec![StyledItem, StyledItem])
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .highlight_style(Style::default().fg(Color::Yellow));
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list, 2, 3, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["a ", "a ", "  "]);
+        let base = Style::default().fg(Color::White).bg(Color::Black);
+        expected.set_style(expected.area, base);
+        expected.set_style(Rect::new(0, 0, 2, 2), StyledItem.item_style());
+        expected.set_style(Rect::new(0, 0, 1, 2), Style::default().bg(Color::Green));
+        expected.set_style(Rect::new(0, 1, 2, 1), Style::default().fg(Color::Yellow));
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn visible_items_and_range() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 2),
+            TestItem::new("c", 2),
+            TestItem::new("d", 1),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        state.select(Some(2));
+
+        // when
+        render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.visible_range(), 1..3);
+        let visible: Vec<_> = list
+            .visible_items(&state)
+            .map(|(i, item)| (i, item.text))
+            .collect();
+        assert_eq!(visible, vec![(1, "b"), (2, "c")]);
+    }
+
+    #[test]
+    fn frozen_bottom() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+            TestItem::new("d", 1),
+        ];
+        let list = List::new(items).frozen_bottom(1);
+        let mut state = ListState::default();
+
+        // when
+        let first = render(list.clone(), 2, 3, &mut state);
+        state.select(Some(2));
+        let second = render(list.clone(), 2, 3, &mut state);
+        state.next();
+        let third = render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(first, Buffer::with_lines(vec!["a ", "b ", "d "]));
+        assert_eq!(second, Buffer::with_lines(vec!["b ", ">c", "d "]));
+        assert_eq!(third, Buffer::with_lines(vec!["b ", "c ", ">d"]));
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn sticky_header() {
+        // given
+        let items = ["H", "a", "b", "c", "d", "e"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec()).sticky_header(1);
+        let mut state = ListState::default();
+
+        // when
+        let first = render(list.clone(), 2, 3, &mut state);
+        for _ in 0..4 {
+            state.next();
+        }
+        let second = render(list.clone(), 2, 3, &mut state);
+
+        // then
+        assert_eq!(first, Buffer::with_lines(vec!["H ", "a ", "b "]));
+        assert_eq!(second, Buffer::with_lines(vec!["H ", "c ", ">d"]));
+        assert_eq!(state.selected(), Some(4));
+        assert_eq!(state.item_at(0, 0), Some(0));
+        assert_eq!(state.item_at(0, 2), Some(4));
+
+        // when the header is selected explicitly
+        state.select(Some(0));
+        let third = render(list.clone(), 2, 3, &mut state);
+        state.next();
+        render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(third, Buffer::with_lines(vec![">H", "c ", "d "]));
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn compact_sticky_header() {
+        // given
+        let items = ["H", "a", "b", "c"].map(|text| TestItem::new(text, 2));
+        let list = List::new(items.to_vec()).sticky_header(1).compact(true);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["H ", "a ", "b "]));
+    }
+
+    #[test]
+    fn sticky_header_with_spacing() {
+        // given
+        let items = ["H", "I", "a", "b"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec()).sticky_header(2).spacing(1);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["H ", "  ", "I ", "a "]));
+    }
+
+    #[test]
+    fn sticky_header_higher_than_viewport() {
+        // given
+        let items = vec![TestItem::new("H", 3), TestItem::new("a", 1)];
+        let list = List::new(items).sticky_header(1);
+
+        // when
+        let buf = render(list, 2, 2, &mut ListState::default());
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["H ", "  "]));
+    }
+
+    #[test]
+    fn opaque_items_are_not_styled_beneath() {
+        // given
+        #[derive(Clone)]
+        struct CardItem(bool);
+
+        impl Listable for CardItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn opaque(&self) -> bool {
+                self.0
+            }
+        }
+
+        impl Widget for CardItem {
+            fn render(self, _: Rect, _: &mut Buffer) {}
+        }
+        let base = Style::default().bg(Color::Black);
+        let list = List::new(vec![CardItem(true), CardItem(false)]).style(base);
+
+        // when
+        let buf = render(list, 2, 3, &mut ListState::default());
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["  ", "  ", "  "]);
+        expected.set_style(Rect::new(0, 1, 2, 2), base);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn selected_item() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let mut list = List::from_slice(&items);
+        let mut state = ListState::default();
+
+        // then
+        assert!(list.selected_item(&state).is_none());
+        state.select(Some(1));
+        assert_eq!(list.selected_item(&state).map(|item| item.text), Some("b"));
+        if let Some(item) = list.selected_item_mut(&state) {
+            item.text = "c";
+        }
+        assert_eq!(list.items()[1].text, "c");
+        assert_eq!(items[1].text, "b");
+    }
+
+    #[test]
+    fn assume_width() {
+        // given
+        #[derive(Clone)]
+        struct CountingItem(Rc<std::cell::Cell<usize>>);
+
+        impl Listable for CountingItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn height_for_width(&self, width: u16) -> usize {
+                self.0.set(self.0.get() + 1);
+                usize::from(width)
+            }
+        }
+
+        impl Widget for CountingItem {
+            fn render(self, _: Rect, _: &mut Buffer) {}
+        }
+        let count = Rc::new(std::cell::Cell::new(0));
+        let list = List::new(vec![CountingItem(Rc::clone(&count)); 3])
+            .wrap(true)
+            .assume_width(2);
+        let mut state = ListState::default();
+        assert_eq!(count.get(), 3);
+
+        // when the width matches
+        render(list.clone(), 2, 4, &mut state);
+
+        // then the heights are reused
+        assert_eq!(count.get(), 3);
+        assert_eq!(state.heights, vec![2, 2, 2]);
+
+        // when the width differs
+        render(list, 3, 4, &mut state);
+
+        // then the heights are measured again
+        assert_eq!(count.get(), 6);
+        assert_eq!(state.heights, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn search() {
+        // given
+        let items = vec![
+            TestItem::new("Apple", 1),
+            TestItem::new("banana", 1),
+            TestItem::new("avocado", 1),
+        ];
+        let list = List::new(items);
+        let matcher = |item: &TestItem, query: &str| {
+            item.text.to_lowercase().starts_with(&query.to_lowercase())
+        };
+        let mut state = ListState::default();
+        state.set_num_elements(list.len());
+
+        // then
+        assert!(list.search(&mut state, "a", matcher));
+        assert_eq!(state.selected(), Some(0));
+        state.next();
+        assert!(list.search(&mut state, "a", matcher));
+        assert_eq!(state.selected(), Some(2));
+        state.next();
+        assert!(list.search(&mut state, "B", matcher));
+        assert_eq!(state.selected(), Some(1));
+        assert!(!list.search(&mut state, "c", matcher));
+        assert_eq!(state.selected(), Some(1));
+
+        let mut state = ListState::default().wrap_policy(crate::WrapPolicy {
+            search: false,
+            ..crate::WrapPolicy::default()
+        });
+        state.select(Some(1));
+        assert!(!list.search(&mut state, "apple", matcher));
+    }
+
+    #[test]
+    fn search_not_highlightable() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items).highlightable(false);
+        let mut state = ListState::default();
+        render(list.clone(), 2, 2, &mut state);
+
+        // when
+        let found = list.search(&mut state, "b", |item, query| item.text == query);
+        let activated =
+            list.select_and_activate_matching(&mut state, "b", |item, query| item.text == query);
+
+        // then
+        assert!(!found);
+        assert_eq!(activated, None);
+        assert_eq!(state.selected(), None);
+        assert_eq!(state.take_activated(), None);
+    }
+
+    #[test]
+    fn jump_to_scroll_on_shrinking_list() {
+        for reversed in [false, true] {
+            // given
+            let items = vec![TestItem::new("x", 1); 10];
+            let mut state = ListState::default();
+            render(List::new(items).reversed(reversed), 2, 3, &mut state);
+            state.select(Some(9));
+            state.jump_to(0, JumpMode::Scroll);
+
+            // when the list shrinks while it scrolls
+            let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+            let buf = render(List::new(items).reversed(reversed), 2, 3, &mut state);
+
+            // then
+            assert_eq!(state.offset, 0);
+            let expected = if reversed {
+                vec!["b ", ">a", "  "]
+            } else {
+                vec![">a", "b ", "  "]
+            };
+            assert_eq!(buf, Buffer::with_lines(expected));
+        }
+    }
+
+    #[test]
+    fn select_and_activate_matching() {
+        // given
+        let items = ["open", "save", "quit", "save as"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec());
+        let matcher = |item: &TestItem, query: &str| item.text.starts_with(query);
+        let mut state = ListState::default().with_selected(Some(2));
+
+        // when
+        let index = list.select_and_activate_matching(&mut state, "s", matcher);
+
+        // then
+        assert_eq!(index, Some(1));
+        assert_eq!(state.selected(), Some(1));
+        assert_eq!(state.take_activated(), Some(1));
+
+        // when
+        let index = list.select_and_activate_matching(&mut state, "x", matcher);
+
+        // then
+        assert_eq!(index, None);
+        assert_eq!(state.selected(), Some(1));
+        assert_eq!(state.take_activated(), None);
+    }
+
+    #[test]
+    fn search_best() {
+        // given
+        let items = vec![
+            TestItem::new("ab", 1),
+            TestItem::new("abc", 1),
+            TestItem::new("bc", 1),
+        ];
+        let list = List::new(items);
+        let score = |item: &TestItem, query: &str| {
+            let matches = item.text.chars().filter(|c| query.contains(*c)).count();
+            (matches > 0).then_some(matches as i64)
+        };
+        let mut state = ListState::default();
+
+        // then
+        assert!(list.search_best(&mut state, "bc", score));
+        assert_eq!(state.selected(), Some(1));
+        assert!(!list.search_best(&mut state, "x", score));
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn scrollbar() {
+        // given
+        let items = ["a", "b", "c", "d", "e", "f"].map(|text| TestItem::new(text, 1));
+        let scrollbar = Scrollbar::default().begin_symbol(None).end_symbol(None);
+        let list = List::new(items.to_vec()).scrollbar(Some(scrollbar));
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list.clone(), 2, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a█", "b█", "c║"]));
+        assert_eq!(state.element_at(1, 0), Some(ListElement::Thumb));
+
+        // when
+        let clicked = state.click(1, 2);
+        let buf = render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(clicked, Some(ListElement::TrackBelow));
+        assert_eq!(state.selected(), None);
+        assert_eq!(buf, Buffer::with_lines(vec!["d║", "e█", "f█"]));
+        assert_eq!(state.element_at(1, 0), Some(ListElement::TrackAbove));
+        assert_eq!(
+            state.scrollbar_state(),
+            ScrollbarState::new(6)
+                .position(6)
+                .viewport_content_length(3)
+        );
+    }
+
+    #[test]
+    fn scroll_indicators() {
+        // given
+        let items: Vec<_> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let list = List::new(items).scroll_indicators(true);
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let first = render(list.clone(), 3, 2, &mut state);
+        state.select(Some(2));
+        let second = render(list.clone(), 3, 2, &mut state);
+
+        // then
+        assert_eq!(first, Buffer::with_lines(vec!["a  ", ">b⤓"]));
+        assert_eq!(second, Buffer::with_lines(vec!["b ⤒", ">c⤓"]));
+        assert_eq!(
+            state.element_at(2, 0),
+            Some(crate::ListElement::TopIndicator)
+        );
+        assert_eq!(
+            state.element_at(2, 1),
+            Some(crate::ListElement::BottomIndicator)
+        );
+        assert_eq!(state.element_at(0, 1), Some(crate::ListElement::Item(2)));
+        assert_eq!(state.element_at(0, 2), None);
+        assert_eq!(state.item_at(0, 0), Some(1));
+
+        // when
+        state.click(2, 1);
+        let third = render(list, 3, 2, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(3));
+        assert_eq!(third, Buffer::with_lines(vec!["c ⤒", ">d "]));
+    }
+
+    #[test]
+    fn highlighted_height() {
+        // given
+        #[derive(Clone)]
+        struct ExpandItem(&'static str, Rc<std::cell::Cell<usize>>);
+
+        impl Listable for ExpandItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn highlighted_height(&self) -> usize {
+                2
+            }
+
+            fn highlight(self) -> Self {
+                self.1.set(self.1.get() + 1);
+                self
+            }
+        }
+
+        impl Widget for ExpandItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                for y in area.top()..area.bottom() {
+                    buf.set_string(area.x, y, self.0, Style::default());
+                }
+            }
+        }
+        let count = Rc::new(std::cell::Cell::new(0));
+        let items = ["a", "b", "c"].map(|text| ExpandItem(text, Rc::clone(&count)));
+        let list = List::new(items.to_vec());
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let buf = render(list.clone(), 1, 3, &mut state);
+
+        // then
+        assert_eq!(state.heights, vec![2, 1, 1]);
+        assert_eq!(buf, Buffer::with_lines(vec!["a", "a", "b"]));
+        assert_eq!(count.get(), 1);
+
+        // when the selected item is scrolled out of view
+        state.scroll_down(2);
+        render(list, 1, 3, &mut state);
+
+        // then it is not highlighted
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn shrinking_highlighted_height() {
+        // given
+        #[derive(Clone)]
+        struct ShrinkItem(&'static str);
+
+        impl Listable for ShrinkItem {
+            fn height(&self) -> usize {
+                2
+            }
+
+            fn highlighted_height(&self) -> usize {
+                1
+            }
+        }
+
+        impl Widget for ShrinkItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                for y in area.top()..area.bottom() {
+                    buf.set_string(area.x, y, self.0, Style::default());
+                }
+            }
+        }
+        let items = vec![ShrinkItem("a"), ShrinkItem("b"), ShrinkItem("c")];
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let top = render(List::new(items.clone()), 1, 5, &mut state);
+        let bottom = render(
+            List::new(items.clone()).start_corner(Corner::BottomLeft),
+            1,
+            5,
+            &mut state,
+        );
+        let reversed = render(List::new(items.clone()).reversed(true), 1, 5, &mut state);
+        let frozen = render(List::new(items).frozen_bottom(2), 1, 5, &mut state);
+
+        // then
+        assert_eq!(top, Buffer::with_lines(vec!["a", "a", "b", "c", "c"]));
+        assert_eq!(bottom, Buffer::with_lines(vec!["c", "c", "b", "a", "a"]));
+        assert_eq!(reversed, Buffer::with_lines(vec!["c", "c", "b", "a", "a"]));
+        assert_eq!(frozen, Buffer::with_lines(vec!["a", "a", "b", "c", "c"]));
+    }
+
+    #[test]
+    fn renders_only_shown_items() {
+        // given
+        #[derive(Clone)]
+        struct CountItem(usize, Rc<std::cell::RefCell<Vec<usize>>>);
+
+        impl Listable for CountItem {
+            fn height(&self) -> usize {
+                2
+            }
+        }
+
+        impl Widget for CountItem {
+            fn render(self, _: Rect, _: &mut Buffer) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+        let rendered = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let items: Vec<_> = (0..10)
+            .map(|i| CountItem(i, Rc::clone(&rendered)))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(5));
+
+        // when
+        render(List::new(items.clone()).wrap(true), 1, 5, &mut state);
+
+        // then
+        assert_eq!(*rendered.borrow(), vec![3, 4, 5]);
+
+        // when
+        rendered.borrow_mut().clear();
+        state.scroll_down(1);
+        render(List::new(items).flow_columns(2), 2, 4, &mut state);
+
+        // then
+        rendered.borrow_mut().sort_unstable();
+        assert_eq!(*rendered.borrow(), vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn render_selected() {
+        // given
+        #[derive(Clone)]
+        struct EditItem(&'static str);
+
+        impl Listable for EditItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn highlighted_height(&self) -> usize {
+                2
+            }
+
+            fn render_selected(self, area: Rect, buf: &mut Buffer, _: &HighlightTheme) {
+                Paragraph::new(format!("[{}]", self.0))
+                    .block(Block::default().borders(Borders::TOP))
+                    .render(area, buf);
+            }
+        }
+
+        impl Widget for EditItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                Paragraph::new(self.0).render(area, buf);
+            }
+        }
+        let list = List::new(vec![EditItem("a"), EditItem("b")]);
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let buf = render(list, 3, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["───", "[a]", "b  "]));
+    }
+
+    #[test]
+    fn debug_overlay() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 2)];
+        let list = List::new(items).debug_overlay(true);
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list, 32, 3, &mut state);
+
+        // then
+        let expected = Buffer::with_lines(vec![
+            "a   off:0 vh:3 sel:1 [0:1] [1:2]",
+            ">b                              ",
+            "                                ",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn debug_overlay_without_rows() {
+        // given
+        let items = vec![TestItem::new("a", 1)];
+        let list = List::new(items).debug_overlay(true);
+        let area = Rect::new(0, 2, 8, 0);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 2));
+
+        // when
+        list.render(area, &mut buf, &mut ListState::default());
+
+        // then
+        assert_eq!(buf, Buffer::empty(Rect::new(0, 0, 8, 2)));
+    }
+
+    #[test]
+    fn gap_style() {
+        // given
+        let items = vec![TestItem::new("a", 1).separated(), TestItem::new("b", 1)];
+        let base = Style::default().bg(Color::White);
+        let gap = Style::default().bg(Color::DarkGray);
+        let list = List::new(items)
+            .separator(Line::from("-"))
+            .style(base)
+            .gap_style(gap);
+
+        // when
+        let buf = render(list, 2, 4, &mut ListState::default());
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["a ", "- ", "b ", "  "]);
+        expected.set_style(expected.area, base);
+        expected.set_style(Rect::new(0, 1, 2, 1), gap);
+        expected.set_style(Rect::new(0, 3, 2, 1), gap);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn items_fitting() {
+        // given
+        let items = vec![
+            TestItem::new("a", 2).separated(),
+            TestItem::new("b", 1),
+            TestItem::new("c", 3),
+        ];
+        let list = List::new(items).separator("-");
+
+        // then
+        assert_eq!(list.items_fitting(0, 1), 0);
+        assert_eq!(list.items_fitting(0, 4), 2);
+        assert_eq!(list.items_fitting(1, 4), 2);
+        assert_eq!(list.items_fitting(3, 4), 0);
+    }
+
+    #[test]
+    fn not_highlightable() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items).highlightable(false);
+        let mut state = ListState::default().with_selected(Some(0));
+        state.toggle_selection(0);
+
+        // when
+        let buf = render(list.clone(), 3, 2, &mut state);
+        state.next();
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", "b  "]));
+        assert_eq!(state.selected(), Some(0));
+        assert!(state.selected_items().contains(&0));
+
+        // when highlightable again
+        let buf = render(list.highlightable(true), 3, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![">*a", "b  "]));
+    }
+
+    #[test]
+    fn compact() {
+        // given
+        #[derive(Clone)]
+        struct CardItem(&'static str, bool);
+
+        impl Listable for CardItem {
+            fn height(&self) -> usize {
+                2
+            }
+
+            fn compact(self) -> Self {
+                Self(self.0, true)
+            }
+        }
+
+        impl Widget for CardItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                let text = if self.1 { self.0.into() } else { "card".into() };
+                Paragraph::new(vec![Line::from(self.0), text]).render(area, buf);
+            }
+        }
+        let items = ["a", "b", "c"].map(|text| CardItem(text, false));
+        let mut state = ListState::default();
+        state.select(Some(2));
+
+        // when
+        let buf = render(List::new(items.to_vec()), 4, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["c   ", "card"]));
+
+        // when
+        let buf = render(List::new(items.to_vec()).compact(true), 4, 2, &mut state);
+
+        // then
+        assert_eq!(state.heights, vec![1, 1, 1]);
+        assert_eq!(buf, Buffer::with_lines(vec!["c   ", "    "]));
+    }
+
+    #[test]
+    fn compact_frozen_bottom() {
+        // given
+        let items = vec![
+            TestItem::new("a", 2),
+            TestItem::new("b", 2),
+            TestItem::new("c", 2),
+        ];
+        let list = List::new(items).compact(true).frozen_bottom(1);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "b ", "c "]));
+    }
+
+    #[test]
+    fn auto_scroll() {
+        // given
+        let items = ["a", "b", "c", "d"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec()).auto_scroll(false);
+        let mut state = ListState::default();
+        state.select(Some(3));
+
+        // when
+        let buf = render(list.clone(), 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.offset, 0);
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "b "]));
+
+        // when
+        state.scroll_down(1);
+        state.select(Some(0));
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.offset, 1);
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", "c "]));
+    }
+
+    #[test]
+    fn layout() {
+        // given
+        let items = vec![
+            TestItem::new("a", 2),
+            TestItem::new("b", 1),
+            TestItem::new("c", 3),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        render(list.clone(), 2, 4, &mut state);
+
+        // when
+        state.scroll_to_row(1);
+        render(list, 2, 4, &mut state);
+
+        // then
+        let item = |index, y, height, truncated_top, truncated_bottom| ItemLayout {
+            index,
+            rect: Rect::new(0, y, 2, height),
+            truncated_top,
+            truncated_bottom,
+        };
+        assert_eq!(
+            state.layout(),
+            [
+                item(0, 0, 1, 1, 0),
+                item(1, 1, 1, 0, 0),
+                item(2, 2, 2, 0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_height_items_are_hidden() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1).separated(),
+            TestItem::new("b", 0).separated(),
+            TestItem::new("c", 1),
+            TestItem::new("d", 0),
+        ];
+        let list = List::new(items).separator("-");
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![">a", "- ", "c ", "  "]));
+        assert_eq!(state.item_at(0, 2), Some(2));
+        assert_eq!(state.item_at(0, 3), None);
+
+        // when
+        state.next();
+        state.next();
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(buf, Buffer::with_lines(vec![">a", "- ", "c ", "  "]));
+        state.select_nearest(3);
+        assert_eq!(state.selected(), Some(2));
+    }
+
+    #[test]
+    fn fill_height() {
+        // given
+        #[derive(Clone)]
+        struct CardItem(&'static str);
+
+        impl Listable for CardItem {
+            fn height(&self) -> usize {
+                2
+            }
+
+            fn fill_height(&self) -> Option<usize> {
+                Some(1)
+            }
+
+            fn item_style(&self) -> Style {
+                Style::default().bg(Color::Blue)
+            }
+        }
+
+        impl Widget for CardItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                Paragraph::new(vec![self.0.into(), self.0.into()]).render(area, buf);
+            }
+        }
+        let list = List::new(vec![CardItem("a"), CardItem("b")])
+            .gap_style(Style::default().bg(Color::Red));
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 1, 4, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["a", " ", "b", " "]);
+        expected.set_style(Rect::new(0, 0, 1, 1), Style::default().bg(Color::Blue));
+        expected.set_style(Rect::new(0, 1, 1, 1), Style::default().bg(Color::Red));
+        expected.set_style(Rect::new(0, 2, 1, 1), Style::default().bg(Color::Blue));
+        expected.set_style(Rect::new(0, 3, 1, 1), Style::default().bg(Color::Red));
+        assert_eq!(state.heights, vec![2, 2]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn move_item() {
+        // given
+        let items = ["a", "b", "c", "d"].map(|text| TestItem::new(text, 1));
+        let mut list = List::new(items.to_vec());
+        let mut state = ListState::default();
+        state.select(Some(1));
+        state.toggle_selection(3);
+
+        // when
+        list.move_item(&mut state, 1, 3);
+
+        // then
+        let texts: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["a", "c", "d", "b"]);
+        assert_eq!(state.selected(), Some(3));
+        assert!(state.selected_items().contains(&2));
+
+        // when
+        list.move_item(&mut state, 2, 0);
+
+        // then
+        let texts: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["d", "a", "c", "b"]);
+        assert_eq!(state.selected(), Some(3));
+        assert!(state.selected_items().contains(&0));
+
+        // when
+        list.swap(&mut state, 0, 3);
+
+        // then
+        let texts: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["b", "a", "c", "d"]);
+        assert_eq!(state.selected(), Some(0));
+        assert!(state.selected_items().contains(&3));
+    }
+
+    #[test]
+    fn drop_indicator() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 2)];
+        let list = List::new(items).drop_indicator("--");
+        let mut state = ListState::default();
+
+        // when
+        state.set_drop_target(Some(1));
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "--", "b ", "  "]));
+
+        // when the target is the first item
+        state.set_drop_target(Some(0));
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["--", "a ", "b ", "  "]));
+
+        // when the target is behind the last item
+        state.set_drop_target(Some(2));
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "b ", "  ", "--"]));
+    }
+
+    #[test]
+    fn drop_indicator_in_gap() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1).separated(),
+            TestItem::new("b", 1).separated(),
+            TestItem::new("c", 1),
+        ];
+        let list = List::new(items).separator("..").drop_indicator("--");
+        let mut state = ListState::default();
+
+        // when
+        state.set_drop_target(Some(1));
+        let buf = render(list, 2, 5, &mut state);
+
+        // then the indicator takes the row of the separator
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "--", "b ", "..", "c "]));
+    }
+
+    #[test]
+    fn drop_indicator_bottom_to_top() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items)
+            .start_corner(Corner::BottomLeft)
+            .drop_indicator("--");
+        let mut state = ListState::default();
+
+        // when
+        state.set_drop_target(Some(0));
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["  ", "b ", "a ", "--"]));
+
+        // when the target is behind the last item
+        state.set_drop_target(Some(2));
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["  ", "--", "b ", "a "]));
+    }
+
+    #[test]
+    fn drop_indicator_reversed() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items).reversed(true).drop_indicator("--");
+        let mut state = ListState::default();
+
+        // when
+        state.set_drop_target(Some(0));
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", "a ", "--", "  "]));
+
+        // when the target is behind the last item
+        state.set_drop_target(Some(2));
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["--", "b ", "a ", "  "]));
+    }
+
+    #[test]
+    fn highlight_symbol_width() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items)
+            .highlight_symbol("🦀")
+            .highlight_symbol_width(3);
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let buf = render(list.clone(), 5, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["🦀 >a", "   b"]));
+
+        // when the gutter is too narrow for the symbol
+        let buf = render(list.highlight_symbol_width(1), 3, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![" >a", " b "]));
+    }
+
+    #[test]
+    fn flow_columns() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 2),
+            TestItem::new("d", 1),
+            TestItem::new("e", 1),
+            TestItem::new("f", 1),
+        ];
+        let list = List::new(items).flow_columns(2);
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list.clone(), 4, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a c ", ">b  "]));
+        assert_eq!(state.visible_range(), 0..3);
+
+        // when the selection moves to the next columns
+        state.next();
+        state.next();
+        let buf = render(list, 4, 2, &mut state);
+
+        // then
+        assert_eq!(state.offset, 3);
+        assert_eq!(buf, Buffer::with_lines(vec![">df ", "e   "]));
+    }
+
+    #[test]
+    fn more_flow_columns_than_width() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items).flow_columns(65536);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 2, 1, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["ab"]));
+    }
+
+    #[test]
+    fn content_hash_caches_heights() {
+        // given
+        #[derive(Clone)]
+        struct HashItem(&'static str, Rc<std::cell::Cell<usize>>);
+
+        impl Listable for HashItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn height_for_width(&self, width: u16) -> usize {
+                self.1.set(self.1.get() + 1);
+                (self.0.len() + width as usize - 1) / width as usize
+            }
+
+            fn content_hash(&self) -> Option<u64> {
+                Some(hash_key(&self.0))
+            }
+        }
+
+        impl Widget for HashItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                Paragraph::new(self.0)
+                    .wrap(Wrap { trim: false })
+                    .render(area, buf);
+            }
+        }
+        let count = Rc::new(std::cell::Cell::new(0));
+        let items = ["abc", "d"].map(|text| HashItem(text, Rc::clone(&count)));
+        let list = List::new(items.to_vec()).wrap(true);
+        let mut state = ListState::default();
+
+        // when
+        render(list.clone(), 2, 3, &mut state);
+        render(list.clone(), 2, 3, &mut state);
+
+        // then
+        assert_eq!(count.get(), 2);
+        assert_eq!(state.heights, vec![2, 1]);
+
+        // when the width changes
+        render(list, 3, 3, &mut state);
+
+        // then the items are measured again
+        assert_eq!(count.get(), 4);
+        assert_eq!(state.heights, vec![1, 1]);
+    }
+
+    #[test]
+    fn clear_block() {
+        // given
+        let items = vec![TestItem::new("a", 1)];
+        let mut list = List::new(items).block(Block::default().borders(Borders::ALL));
+        let mut state = ListState::default();
+
+        // when
+        list.clear_block();
+        let buf = render(list, 2, 1, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a "]));
+    }
+
+    #[test]
+    fn items_seen() {
+        // given
+        let items = vec![
+            TestItem::new("a", 2),
+            TestItem::new("b", 4),
+            TestItem::new("c", 4),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        render(list.clone(), 1, 5, &mut state);
+
+        // when
+        state.scroll_to_row(1);
+        render(list, 1, 5, &mut state);
+
+        // then
+        assert_eq!(state.items_seen(1.0), vec![1]);
+        assert_eq!(state.items_seen(0.5), vec![0, 1]);
+        assert_eq!(state.items_seen(0.0), vec![0, 1]);
+    }
+
+    #[test]
+    fn default_item_height() {
+        // given
+        let items = vec![TestItem::new("a", 0), TestItem::new("b", 1)];
+        let list = List::new(items).default_item_height(2);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list, 1, 3, &mut state);
+
+        // then
+        assert_eq!(state.heights, vec![2, 1]);
+        assert_eq!(buf, Buffer::with_lines(vec!["a", " ", "b"]));
+    }
+
+    #[test]
+    fn with_selected_mut() {
+        // given
+        let item = |text| WrapItem { text, wrap: false };
+        let items = vec![item("a"), item("b")];
+        let mut list = List::new(items).wrap(true).assume_width(2);
+        let mut state = ListState::default();
+        assert!(!list.with_selected_mut(&state, |_| {}));
+        state.select(Some(1));
+
+        // when
+        let edited = list.with_selected_mut(&state, |item| item.text = "bbb");
+
+        // then
+        assert!(edited);
+        assert_eq!(list.assumed_heights, Some((2, vec![1, 2])));
+    }
+
+    #[test]
+    fn scroll_strategy() {
+        // given
+        let items: Vec<_> = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let list = List::new(items).programmatic_scroll_strategy(ScrollStrategy::Center);
+        let mut state = ListState::default();
+
+        // when selected programmatically
+        state.select(Some(6));
+        render(list.clone(), 2, 5, &mut state);
+
+        // then the selection is centered
+        assert_eq!(state.offset, 4);
+
+        // when the user navigates
+        state.next();
+        render(list.clone(), 2, 5, &mut state);
+
+        // then the viewport scrolls only at the edge
+        assert_eq!(state.offset, 4);
+
+        // when the user navigates with centering
+        let list = list.scroll_strategy(ScrollStrategy::AlwaysCenter);
+        state.previous();
+        render(list.clone(), 2, 5, &mut state);
+
+        // then the selection stays centered
+        assert_eq!(state.offset, 4);
+        state.previous();
+        render(list.clone(), 2, 5, &mut state);
+        assert_eq!(state.offset, 3);
+
+        // when the selection is shown and centered only if needed
+        let list = list.scroll_strategy(ScrollStrategy::Center);
+        state.previous();
+        render(list.clone(), 2, 5, &mut state);
+
+        // then the viewport does not move
+        assert_eq!(state.offset, 3);
+
+        // when an item near the end is centered
+        state.select(Some(9));
+        render(list, 2, 5, &mut state);
+
+        // then no blank rows are left below the last item
+        assert_eq!(state.offset, 5);
+    }
+
+    #[test]
+    fn update_keyed() {
+        // given
+        let mut list = List::new(vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ]);
+        let mut state = ListState::default();
+        state.select(Some(1));
+        list.update_keyed(
+            vec![
+                TestItem::new("a", 1),
+                TestItem::new("b", 1),
+                TestItem::new("c", 1),
+            ],
+            &mut state,
+        );
+        while state.is_animating() {
+            render(list.clone(), 3, 4, &mut state);
+        }
+
+        // when
+        list.update_keyed(
+            vec![
+                TestItem::new("x", 1),
+                TestItem::new("a", 1),
+                TestItem::new("c", 1),
+            ],
+            &mut state,
+        );
+        let buf = render(list.clone(), 3, 4, &mut state);
+
+        // then
+        let keys: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(keys, vec!["x", "a", "b", "c"]);
+        assert_eq!(state.selected(), Some(3));
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let mut expected = Buffer::with_lines(vec!["x  ", "a  ", "b  ", ">c "]);
+        expected.set_style(Rect::new(0, 0, 3, 1), dim);
+        expected.set_style(Rect::new(0, 2, 3, 1), dim);
+        assert_eq!(buf, expected);
+
+        // when
+        while state.is_animating() {
+            render(list.clone(), 3, 4, &mut state);
+        }
+        let buf = render(list.clone(), 3, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["x  ", "a  ", ">c ", "   "]));
+    }
+
+    #[test]
+    fn apply_drag() {
+        // given
+        let items = vec![
+            LinesItem::new("a"),
+            LinesItem::new("b"),
+            LinesItem::new("c"),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        render(list.clone(), 2, 3, &mut state);
+
+        // when
+        let rows = state.apply_drag(1);
+        let buf = render(list, 2, 3, &mut state);
+
+        // then
+        assert_eq!(rows, 1);
+        assert_eq!(buf, Buffer::with_lines(vec!["a1", "b0", "b1"]));
+    }
+
+    #[test]
+    fn items_shrink_under_top_clip() {
+        // given
+        let items = vec![
+            TestItem::new("a", 5),
+            TestItem::new("b", 5),
+            TestItem::new("c", 5),
+        ];
+        let mut state = ListState::default();
+        render(List::new(items).auto_scroll(false), 3, 3, &mut state);
+        state.scroll_to_row(3);
+
+        // when the first item shrinks beneath the rows that are cut off
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 5),
+            TestItem::new("c", 5),
+        ];
+        let buf = render(List::new(items).auto_scroll(false), 3, 3, &mut state);
+
+        // then
+        assert_eq!((state.offset, state.top_clip), (0, 0));
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", "b  ", "   "]));
+    }
+
+    #[test]
+    fn items_shrink_under_top_clip_bottom_to_top() {
+        // given
+        let items = vec![
+            TestItem::new("a", 5),
+            TestItem::new("b", 5),
+            TestItem::new("c", 5),
+        ];
+        let list = List::new(items)
+            .auto_scroll(false)
+            .start_corner(Corner::BottomLeft);
+        let mut state = ListState::default();
+        render(list, 3, 3, &mut state);
+        state.scroll_to_row(4);
+
+        // when the first item shrinks beneath the rows that are cut off
+        let items = vec![
+            TestItem::new("a", 2),
+            TestItem::new("b", 5),
+            TestItem::new("c", 5),
+        ];
+        let list = List::new(items)
+            .auto_scroll(false)
+            .start_corner(Corner::BottomLeft);
+        let buf = render(list, 3, 3, &mut state);
+
+        // then
+        assert_eq!((state.offset, state.top_clip), (0, 0));
+        assert_eq!(buf, Buffer::with_lines(vec!["b  ", "a  ", "   "]));
+    }
+
+    #[test]
+    fn content_changes_after_drag() {
+        // given
+        let mut state = ListState::default();
+        render(List::new(vec![TestItem::new("a", 3)]), 4, 1, &mut state);
+        state.apply_drag(2);
+
+        // when
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 3)];
+        let buf = render(List::new(items), 1, 2, &mut state);
+
+        // then
+        assert_eq!(state.top_clip, 0);
+        assert_eq!(buf, Buffer::with_lines(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn block_title_fn() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::TOP))
+            .block_title_fn(|item| Line::from(item.map_or("-", |item| item.text)));
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let selected = render(list.clone(), 3, 2, &mut state);
+        state.select(None);
+        let none = render(list, 3, 2, &mut state);
+
+        // then
+        assert_eq!(selected, Buffer::with_lines(vec!["b──", ">b "]));
+        assert_eq!(none, Buffer::with_lines(vec!["-──", "a  "]));
+    }
+
+    #[test]
+    fn wrap_items() {
+        // given
+        let items = vec![
+            WrapItem {
+                text: "abcd",
+                wrap: false,
+            },
+            WrapItem {
+                text: "ef",
+                wrap: false,
+            },
+        ];
+        let mut state = ListState::default();
+
+        // when
+        let wrapped = render(List::new(items.clone()).wrap(true), 2, 4, &mut state);
+        let clipped = render(List::new(items), 2, 4, &mut state);
+
+        // then
+        assert_eq!(wrapped, Buffer::with_lines(vec!["ab", "cd", "ef", "  "]));
+        assert_eq!(clipped, Buffer::with_lines(vec!["ab", "ef", "  ", "  "]));
+    }
+
+    #[test]
+    fn last() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 3),
+            TestItem::new("c", 1),
+            TestItem::new("d", 2),
+        ];
+        let list = List::new(items);
+        let mut state = ListState::default();
+        render(list.clone(), 2, 4, &mut state);
+
+        // when
+        state.last();
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(3));
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", "c ", ">d", "  "]));
+    }
+
+    #[test]
+    fn page_down_and_page_up() {
+        // given
+        let items = [1, 2, 1, 1, 3, 1]
+            .iter()
+            .zip(["a", "b", "c", "d", "e", "f"])
+            .map(|(&height, text)| TestItem::new(text, height))
+            .collect();
+        let list = List::new(items);
+        let mut state = ListState::default().with_selected(Some(0));
+        render(list.clone(), 2, 4, &mut state);
+
+        // when
+        state.page_down();
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec![">c", "d ", "e ", "  "]));
+
+        // when
+        state.page_down();
+        state.page_down();
+        render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(5));
+
+        // when
+        state.select(Some(2));
+        state.offset = 2;
+        render(list.clone(), 2, 4, &mut state);
+        state.page_up();
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(buf, Buffer::with_lines(vec![">a", "b ", "  ", "c "]));
+    }
+
+    #[test]
+    fn circular_offset() {
+        // given
+        let items = ["a", "b", "c", "d", "e"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec());
+        let mut state = ListState::default().circular(true);
+        state.select(Some(4));
+        render(list.clone(), 2, 2, &mut state);
+
+        // when
+        state.next();
+        let buf = render(list.clone(), 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(state.offset, 0);
+        assert_eq!(buf, Buffer::with_lines(vec![">a", "b "]));
+
+        // when
+        state.previous();
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(4));
+        assert_eq!(state.offset, 3);
+        assert_eq!(buf, Buffer::with_lines(vec!["d ", ">e"]));
+    }
+
+    #[test]
+    fn spacing() {
+        // given
+        let items = ["a", "b", "c"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec())
+            .spacing(1)
+            .style(Style::default().bg(Color::Blue));
+        let mut state = ListState::default();
+        state.select(Some(2));
+
+        // when
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["  ", "b ", "  ", ">c"]);
+        expected.set_style(Rect::new(0, 0, 2, 4), Style::default().bg(Color::Blue));
+        assert_eq!(buf, expected);
+        assert_eq!(state.heights, vec![2, 2, 1]);
+        assert_eq!(list.items_fitting(0, 4), 2);
+    }
+
+    #[test]
+    fn scroll_to() {
+        // given
+        let items = [1, 1, 2, 1, 3]
+            .iter()
+            .zip(["a", "b", "c", "d", "e"])
+            .map(|(&height, text)| TestItem::new(text, height))
+            .collect();
+        let list = List::new(items);
+        let mut state = ListState::default().with_selected(Some(0));
+        render(list.clone(), 2, 4, &mut state);
+
+        // when
+        state.scroll_to(4);
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(state.offset, 3);
+        assert_eq!(buf, Buffer::with_lines(vec!["d ", "e ", "  ", "  "]));
+
+        // when
+        state.scroll_to(2);
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(state.offset, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["c ", "  ", "d ", "e "]));
+
+        // when the selection moves
+        state.next();
+        render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.offset, 1);
+    }
+
+    #[test]
+    fn scroll_padding() {
+        // given
+        let items = ["a", "b", "c", "d", "e", "f"].map(|text| TestItem::new(text, 1));
+        let list = List::new(items.to_vec()).scroll_padding(1);
+        let mut state = ListState::default();
+        state.set_num_elements(items.len());
+
+        // when
+        for _ in 0..3 {
+            state.next();
+        }
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", "b ", ">c", "d "]));
+
+        // when
+        state.next();
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", "c ", ">d", "e "]));
+
+        // when the selection is near the end
+        state.last();
+        let buf = render(list.clone(), 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["c ", "d ", "e ", ">f"]));
+
+        // when
+        state.previous();
+        state.previous();
+        state.previous();
+        let buf = render(list, 2, 4, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["b ", ">c", "d ", "e "]));
+    }
+
+    #[test]
+    fn horizontal_without_widths() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items).direction(ListDirection::Horizontal);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list.clone(), 3, 1, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  "]));
+
+        // when
+        state.select(Some(1));
+        let buf = render(list, 3, 1, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![">b "]));
+        assert_eq!(state.visible_range(), 1..2);
+    }
+
+    #[test]
+    fn horizontal() {
+        // given
+        #[derive(Clone)]
+        struct CardItem(&'static str, bool);
+
+        impl Listable for CardItem {
+            fn height(&self) -> usize {
+                2
+            }
+
+            fn width(&self) -> Option<usize> {
+                Some(4)
+            }
+
+            fn highlight(mut self) -> Self {
+                self.1 = true;
+                self
+            }
+        }
+
+        impl Widget for CardItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                let marker = if self.1 { ">" } else { " " };
+                Paragraph::new(format!("{marker}[{}]", self.0)).render(area, buf);
+            }
+        }
+        let items = vec![
+            CardItem("a", false),
+            CardItem("b", false),
+            CardItem("c", false),
+        ];
+        let list = List::new(items).direction(ListDirection::Horizontal);
+        let mut state = ListState::default();
+
+        // when
+        let buf = render(list.clone(), 10, 1, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![" [a] [b] ["]));
+        assert_eq!(state.layout()[2].rect, Rect::new(8, 0, 2, 1));
+        assert_eq!(state.layout()[2].truncated_bottom, 2);
+
+        // when
+        state.next();
+        state.next();
+        state.next();
+        let buf = render(list, 10, 1, &mut state);
+
+        // then
+        assert_eq!(state.selected(), Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec![" [b]>[c]  "]));
+        assert_eq!(state.viewport_height(), Some(1));
+    }
+
+    #[test]
+    fn group_start() {
+        // given
+        let items = vec![
+            TestItem::new("a1", 1),
+            TestItem::new("a2", 1),
+            TestItem::new("b1", 1),
+            TestItem::new("c1", 1),
+            TestItem::new("c2", 1),
+        ];
+        let list = List::new(items);
+        let group_of = |item: &TestItem| item.text.chars().next();
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        assert!(list.next_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(2));
+        assert!(list.next_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(3));
+        assert!(list.next_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(0));
+
+        // then
+        state.select(Some(4));
+        assert!(list.previous_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(2));
+        assert!(list.previous_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(0));
+        assert!(list.previous_group_start(&mut state, group_of));
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn group_start_without_wrapping() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let list = List::new(items);
+        let group_of = |item: &TestItem| item.text;
+        let mut state = ListState::default().circular(false);
+        state.select(Some(1));
+
+        // when
+        let moved = list.next_group_start(&mut state, group_of);
+
+        // then
+        assert!(!moved);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn select_by_key() {
+        // given
+        let items: Vec<_> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let mut state = ListState::default();
+        render(List::new(items.clone()), 1, 2, &mut state);
+        let list = List::new(items);
+
+        // when
+        let found = list.select_by_key(&mut state, &"d");
+
+        // then
+        assert!(found);
+        assert_eq!(state.selected(), Some(3));
+        assert_eq!(state.offset, 2);
+        assert!(!list.select_by_key(&mut state, &"x"));
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn restore_selection() {
+        // given
+        let items: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(1));
+        List::new(items.clone()).remember_selection(&mut state);
+
+        // when
+        let empty = List::<TestItem>::new(vec![]);
+        empty.remember_selection(&mut state);
+        let restored_empty = empty.restore_selection(&mut state);
+        let refilled = List::new(items[1..].to_vec());
+        let restored = refilled.restore_selection(&mut state);
+
+        // then
+        assert!(!restored_empty);
+        assert!(restored);
+        assert_eq!(state.selected(), Some(0));
+    }
+
+    #[test]
+    fn overscroll_end() {
+        // given
+        let items: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|text| TestItem::new(text, 1))
+            .collect();
+        let mut state = ListState::default();
+        state.scroll_down(5);
+
+        // when
+        let clamped = render(List::new(items.clone()), 1, 2, &mut state);
+        state.scroll_down(5);
+        let overscrolled = render(List::new(items).overscroll_end(true), 1, 2, &mut state);
+
+        // then
+        assert_eq!(clamped, Buffer::with_lines(vec!["b", "c"]));
+        assert_eq!(overscrolled, Buffer::with_lines(vec!["c", " "]));
+    }
+
+    #[test]
+    fn viewport_height() {
+        // given
+        let list =
+            List::new(vec![TestItem::new("a", 1)]).block(Block::default().borders(Borders::ALL));
+        let mut state = ListState::default();
+
+        // when
+        render(list, 3, 5, &mut state);
+
+        // then
+        assert_eq!(state.viewport_height(), Some(3));
+    }
+
+    #[test]
+    fn highlight_symbol() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 2)];
+        let list = List::new(items)
+            .style(Style::default().fg(Color::White))
+            .highlight_symbol(">>")
+            .highlight_symbol_style(Style::default().bg(Color::Red));
+        let mut state = ListState::default();
+        state.select(Some(1));
+
+        // when
+        let buf = render(list, 4, 3, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["  a ", ">>>b", "    "]);
+        expected.set_style(expected.area, Style::default().fg(Color::White));
+        expected.set_style(Rect::new(0, 1, 2, 1), Style::default().bg(Color::Red));
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn highlight_symbol_zero_width() {
+        // given
+        #[derive(Clone)]
+        struct NodeItem(Option<&'static str>);
+
+        impl Listable for NodeItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn highlight_symbol(&self) -> Option<&str> {
+                self.0
+            }
+        }
+
+        impl Widget for NodeItem {
+            fn render(self, _: Rect, _: &mut Buffer) {}
+        }
+        let items = vec![NodeItem(Some("▸")), NodeItem(None)];
+        let list = List::new(items)
+            .highlight_symbol(">")
+            .highlight_symbol_style(Style::default().add_modifier(Modifier::BOLD));
+        let area = Rect::new(10, 0, 0, 4);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 4));
+        let mut state = ListState::default();
+
+        // when the list is at the right edge of the buffer
+        for selected in [0, 1] {
+            state.select(Some(selected));
+            list.clone().render(area, &mut buf, &mut state);
+            list.clone()
+                .highlight_symbol_width(2)
+                .render(area, &mut buf, &mut state);
+        }
+
+        // then
+        assert_eq!(buf, Buffer::empty(Rect::new(0, 0, 10, 4)));
+    }
+
+    #[test]
+    fn highlight_symbol_per_item() {
+        // given
+        #[derive(Clone)]
+        struct NodeItem(&'static str, Option<&'static str>);
+
+        impl Listable for NodeItem {
+            fn height(&self) -> usize {
+                1
+            }
+
+            fn highlight_symbol(&self) -> Option<&str> {
+                self.1
+            }
+        }
+
+        impl Widget for NodeItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                buf.set_string(area.x, area.y, self.0, Style::default());
+            }
+        }
+        let items = vec![NodeItem("a", Some("▸▸")), NodeItem("b", None)];
+        let list = List::new(items).highlight_symbol(">");
+        let mut state = ListState::default();
+
+        // when
+        state.select(Some(0));
+        let first = render(list.clone(), 3, 2, &mut state);
+        state.select(Some(1));
+        let second = render(list, 3, 2, &mut state);
+
+        // then
+        assert_eq!(first, Buffer::with_lines(vec!["▸▸a", "  b"]));
+        assert_eq!(second, Buffer::with_lines(vec!["  a", "> b"]));
+    }
+
+    #[test]
+    fn render_shared_items() {
+        // given
+        let items: Arc<[TestItem]> = Arc::from(vec![TestItem::new("a", 1), TestItem::new("b", 1)]);
+        let list = List::from_arc(Arc::clone(&items));
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let clone = list.clone();
+        let buf = render(list, 2, 2, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec![">a", "b "]));
+        assert_eq!(clone.items().as_ptr(), items.as_ptr());
+    }
+
+    #[test]
+    fn highlight_full_width() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let theme = HighlightTheme::new(Style::default().bg(Color::Blue));
+        let list = List::new(items)
+            .highlight_theme(theme)
+            .highlight_symbol("*")
+            .highlight_full_width(true);
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        // when
+        let buf = render(list, 3, 2, &mut state);
+
+        // then
+        let mut expected = Buffer::with_lines(vec!["*>a", " b "]);
+        expected.set_style(Rect::new(0, 0, 3, 1), Style::default().bg(Color::Blue));
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn extend_items() {
+        // given
+        let items = vec![TestItem::new("a", 1)];
+        let mut list = List::from_slice(&items);
+
+        // when
+        list.push(TestItem::new("b", 1));
+        list.extend_items(vec![TestItem::new("c", 1), TestItem::new("d", 1)]);
+
+        // then
+        let texts: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["a", "b", "c", "d"]);
+        assert_eq!(items.len(), 1);
+    }
+
+    #[test]
+    fn items_mut() {
+        // given
+        let items = vec![TestItem::new("a", 1), TestItem::new("b", 1)];
+        let mut list = List::from_slice(&items);
+
+        // when
+        list.items_mut().retain(|item| item.text != "a");
+        list.items_mut()[0].text = "c";
+
+        // then
+        let texts: Vec<_> = list.items().iter().map(|item| item.text).collect();
+        assert_eq!(texts, vec!["c"]);
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn mark_selected_items() {
+        // given
+        let items = vec![
+            TestItem::new("a", 1),
+            TestItem::new("b", 1),
+            TestItem::new("c", 1),
+        ];
+        let mut state = ListState::default();
+        state.select(Some(1));
+        state.toggle_selection(1);
+        state.toggle_selection(2);
+
+        // when
+        let buf = render(List::new(items), 3, 3, &mut state);
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a  ", ">*b", "*c "]));
+    }
+
+    #[test]
+    fn from_exact_iter() {
+        // when
+        let list = List::from_exact_iter(["a", "b"].into_iter().map(|text| TestItem::new(text, 1)));
+
+        // then
+        assert_eq!(list.len(), 2);
+        assert!(matches!(&list.items, Items::Owned(items) if items.capacity() == 2));
+    }
+
+    #[derive(Debug, Clone)]
+    struct LinesItem {
+        lines: Vec<&'static str>,
+    }
+
+    impl LinesItem {
+        fn new(name: &'static str) -> Self {
+            let lines = match name {
+                "a" => vec!["a0", "a1"],
+                "b" => vec!["b0", "b1"],
+                _ => vec!["c0", "c1"],
+            };
+            Self { lines }
+        }
+    }
+
+    impl Listable for LinesItem {
+        fn height(&self) -> usize {
+            self.lines.len()
+        }
+
+        fn truncate_top(mut self, hidden_rows: usize) -> Self {
+            self.lines.drain(..hidden_rows);
+            self
+        }
+    }
+
+    impl Widget for LinesItem {
+        fn render(self, area: Rect, buf: &mut Buffer) {
+            let lines: Vec<Line> = self.lines.into_iter().map(Line::from).collect();
+            Paragraph::new(lines).render(area, buf);
+        }
+    }
+
+    fn render_direction(reversed: bool, start_corner: Corner, selected: Option<usize>) -> Buffer {
+        let items = vec![
+            LinesItem::new("a"),
+            LinesItem::new("b"),
+            LinesItem::new("c"),
+        ];
+        let list = List::new(items)
+            .reversed(reversed)
+            .start_corner(start_corner);
+        let mut state = ListState::default();
+        state.select(selected);
+        render(list, 2, 3, &mut state)
+    }
+
+    #[test]
+    fn truncate_top_to_bottom() {
+        let buf = render_direction(false, Corner::TopLeft, Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec!["b1", "c0", "c1"]));
+    }
+
+    #[test]
+    fn truncate_top_to_bottom_reversed() {
+        let buf = render_direction(true, Corner::TopLeft, Some(0));
+        assert_eq!(buf, Buffer::with_lines(vec!["b1", "a0", "a1"]));
+    }
+
+    #[test]
+    fn truncate_bottom_to_top() {
+        let buf = render_direction(false, Corner::BottomLeft, Some(2));
+        assert_eq!(buf, Buffer::with_lines(vec!["c0", "c1", "b0"]));
+
+        let buf = render_direction(false, Corner::BottomLeft, None);
+        assert_eq!(buf, Buffer::with_lines(vec!["b1", "a0", "a1"]));
+    }
+
+    #[test]
+    fn truncate_bottom_to_top_reversed() {
+        let buf = render_direction(true, Corner::BottomLeft, Some(0));
+        assert_eq!(buf, Buffer::with_lines(vec!["a0", "a1", "b0"]));
+
+        let buf = render_direction(true, Corner::BottomLeft, None);
+        assert_eq!(buf, Buffer::with_lines(vec!["b1", "c0", "c1"]));
+    }
+
+    #[test]
+    fn truncate_bottom_hook() {
+        // given
+        #[derive(Clone)]
+        struct EllipsisItem(Vec<&'static str>);
+
+        impl Listable for EllipsisItem {
+            fn height(&self) -> usize {
+                self.0.len()
+            }
+
+            fn truncate_bottom(mut self, hidden_rows: usize) -> Self {
+                self.0.truncate(self.0.len() - hidden_rows);
+                if let Some(last) = self.0.last_mut() {
+                    *last = "..";
+                }
+                self
+            }
+        }
+
+        impl Widget for EllipsisItem {
+            fn render(self, area: Rect, buf: &mut Buffer) {
+                let lines: Vec<Line> = self.0.into_iter().map(Line::from).collect();
+                Paragraph::new(lines).render(area, buf);
+            }
+        }
+        let items = vec![
+            EllipsisItem(vec!["a0", "a1"]),
+            EllipsisItem(vec!["b0", "b1", "b2"]),
+        ];
+
+        // when
+        let buf = render(List::new(items), 2, 4, &mut ListState::default());
+
+        // then
+        assert_eq!(buf, Buffer::with_lines(vec!["a0", "a1", "b0", ".."]));
+    }
+
+    #[test]
+    fn bottom_to_top_short_list() {
+        let items = vec![LinesItem::new("a")];
+        let list = List::new(items)
+            .reversed(true)
+            .start_corner(Corner::BottomLeft);
+        let buf = render(list, 2, 3, &mut ListState::default());
+        assert_eq!(buf, Buffer::with_lines(vec!["  ", "a0", "a1"]));
     }
 }